@@ -5,6 +5,7 @@ use crate::objects::{
 };
 use crate::{app_error::AppError, app_state};
 use actix_web::{http::header::AUTHORIZATION, web};
+use jsonforms::json_forms::JsonFormsSerializable;
 
 pub async fn request_handler(
     app_state: web::Data<app_state::Data>,
@@ -13,6 +14,7 @@ pub async fn request_handler(
     match req {
         InfoRequest::JsonForms(r) => json_forms::get(r),
         InfoRequest::UsersLogin(r) => {
+            r.validate()?;
             let login = {
                 let users = app_state.user_list.read().unwrap();
                 users.login(&r)
@@ -4,6 +4,7 @@ use actix_web::{
     HttpResponse,
 };
 use derive_more::{Display, Error};
+use jsonforms::json_forms::JsonFormsError;
 #[derive(Debug, Display, Error)]
 pub enum AppError {
     #[display(fmt = "Invalid Input Data: {field} {msg}")]
@@ -11,6 +12,13 @@ pub enum AppError {
         field: &'static str,
         msg: &'static str,
     },
+    #[display(
+        fmt = "Invalid Form Data: {}",
+        "errors.iter().map(ToString::to_string).collect::<Vec<_>>().join(\", \")"
+    )]
+    FormError {
+        errors: Vec<JsonFormsError>,
+    },
     #[display(fmt = "Internal Error {msg}")]
     InternalError {
         msg: String,
@@ -28,6 +36,12 @@ impl From<actix_web::error::BlockingError> for AppError {
     }
 }
 
+impl From<Vec<JsonFormsError>> for AppError {
+    fn from(errors: Vec<JsonFormsError>) -> Self {
+        AppError::FormError { errors }
+    }
+}
+
 impl From<std::fmt::Error> for AppError {
     fn from(err: std::fmt::Error) -> Self {
         let err_msg = format!("format Error {:?}", err);
@@ -46,13 +60,19 @@ impl From<diesel::result::Error> for AppError {
 
 impl error::ResponseError for AppError {
     fn error_response(&self) -> HttpResponse {
+        // the client shows form errors on the fields at their json pointer
+        if let AppError::FormError { errors } = self {
+            return HttpResponse::build(self.status_code()).json(errors);
+        }
         HttpResponse::build(self.status_code())
             .insert_header(ContentType::html())
             .body(self.to_string())
     }
     fn status_code(&self) -> StatusCode {
         match *self {
-            AppError::ValidationError { .. } => StatusCode::BAD_REQUEST,
+            AppError::ValidationError { .. } | AppError::FormError { .. } => {
+                StatusCode::BAD_REQUEST
+            }
             AppError::InvalidToken | AppError::InvalidUser => StatusCode::UNAUTHORIZED,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
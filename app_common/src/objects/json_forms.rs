@@ -5,10 +5,19 @@ use jsonforms::json_forms::{JsonFormsSerializable, *};
 #[macro_export]
 macro_rules! JsonFormGUI {
     ($e:expr, $t:ty,$s:expr) => {
+        $crate::JsonFormGUI!(@form $e, $t, Some($s))
+    };
+    // the title set on the form struct
+    ($e:expr, $t:ty) => {
+        $crate::JsonFormGUI!(@form $e, $t, None::<&str>)
+    };
+    (@form $e:expr, $t:ty, $s:expr) => {
         if $e == stringify!($t) {
-            let mut form = JsonFormsResponse::from(<$t>::jsonforms_schema());
+            let mut form = JsonFormsResponse::from(<$t>::jsonforms_static());
             <$t>::add_buttons(&mut form);
-            form.title = Some($s.to_owned());
+            if let Some(title) = $s {
+                form.title = Some(title.to_owned());
+            }
             return Ok(InfoResponse {
                 response: Some(ApiResponse::JsonForms(form)),
                 user_state: None,
@@ -18,8 +27,8 @@ macro_rules! JsonFormGUI {
 }
 
 pub(crate) fn get(r: JsonFormsRequest) -> Result<InfoResponse, AppError> {
-    JsonFormGUI!(r.name.as_str(), users::LoginRequest, "Login");
-    JsonFormGUI!(r.name.as_str(), users::NewRequest, "User Sign Up");
+    JsonFormGUI!(r.name.as_str(), users::LoginRequest);
+    JsonFormGUI!(r.name.as_str(), users::NewRequest);
 
     let str_err = format!("unknown requested json schema {}", r.name);
     log::error!("{str_err}");
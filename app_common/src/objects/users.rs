@@ -42,7 +42,7 @@ pub(crate) fn db_new_user(
             })
         }
         Err(_) => {
-            check_password_requirements(&req.password, &req.confirm_password)?;
+            req.validate()?;
             let psha256 = compute_sha256(&req.password)?;
 
             let new_user = UserNew {
@@ -79,45 +79,25 @@ pub(crate) fn db_new_user(
     }
 }
 
-fn check_password_requirements(password: &str, confirm_password: &str) -> Result<(), AppError> {
+/// `validate_with` hook of the NewRequest password
+fn check_password_requirements(password: &str, _req: &NewRequest) -> Result<(), &'static str> {
     if password.len() < 8 {
-        return Err(AppError::ValidationError {
-            field: "New Password",
-            msg: "weak, less then 8 characters",
-        });
+        return Err("weak, less then 8 characters");
     }
     if !password.chars().any(|c| c.is_uppercase()) {
-        return Err(AppError::ValidationError {
-            field: "New Password",
-            msg: "weak, no upper case letter found",
-        });
+        return Err("weak, no upper case letter found");
     }
     if !password.chars().any(|c| c.is_lowercase()) {
-        return Err(AppError::ValidationError {
-            field: "New Password",
-            msg: "weak, no lower case letter found",
-        });
+        return Err("weak, no lower case letter found");
     }
     if !password.chars().any(|c| c.is_ascii_digit()) {
-        return Err(AppError::ValidationError {
-            field: "New Password",
-            msg: "weak, no digit found",
-        });
+        return Err("weak, no digit found");
     }
     lazy_static! {
         static ref RE_PUNCT: Regex = Regex::new(r#"[.,/#!$%\\^&\*;:{}=\-_`~()@]"#).unwrap();
     }
     if !RE_PUNCT.is_match(password) {
-        return Err(AppError::ValidationError {
-            field: "New Password",
-            msg: r#"weak, no punctuation found. Use some of .,\/#!$%\\^&\*;:{}=\-_`~()@"#,
-        });
-    }
-    if password != confirm_password {
-        return Err(AppError::ValidationError {
-            field: "New Password",
-            msg: r#"does not match Confirm Password"#,
-        });
+        return Err(r#"weak, no punctuation found. Use some of .,\/#!$%\\^&\*;:{}=\-_`~()@"#);
     }
     Ok(())
 }
@@ -344,10 +324,12 @@ impl User {
 }
 
 #[derive(Deserialize, JsonForms)]
+#[jsonforms(title = "Login")]
 pub struct LoginRequest {
-    #[jsonforms(schema = r#""minLength": 4, "title":"Login User""#)]
+    #[jsonforms(min_length = 4, title = "Login User")]
     pub user: String,
-    #[jsonforms(schema = r#""passwordFmt":1, "format":"password", "title":"Login Password""#)]
+    #[jsonforms(format = "password", title = "Login Password")]
+    #[jsonforms(schema = r#""passwordFmt":1"#)]
     pub password: String,
 }
 
@@ -376,14 +358,18 @@ pub struct LoginResponse {
 }
 
 #[derive(Deserialize, JsonForms)]
+#[jsonforms(title = "User Sign Up")]
 pub struct NewRequest {
-    #[jsonforms(schema = r#""minLength": 4, "title":"Login User""#)]
+    #[jsonforms(min_length = 4, title = "Login User")]
     pub user: String,
-    #[jsonforms(schema = r#""minLength": 4"#)]
+    #[jsonforms(min_length = 4)]
     pub name: String,
-    #[jsonforms(schema = r#""passwordFmt":2, "format":"password", "title":"New Password""#)]
+    #[jsonforms(format = "password", title = "New Password")]
+    #[jsonforms(schema = r#""passwordFmt":2"#)]
+    #[jsonforms(validate_with = "check_password_requirements")]
     pub password: String,
-    #[jsonforms(schema = r#""passwordFmt":1, "format":"password", "title":"Confirm Password""#)]
+    #[jsonforms(format = "password", title = "Confirm Password")]
+    #[jsonforms(schema = r#""passwordFmt":1"#, must_equal = "password")]
     pub confirm_password: String,
 }
 
@@ -1,5 +1,6 @@
 import { Fragment, useState, useEffect, useCallback } from 'react';
 import { JsonForms } from '@jsonforms/react';
+import { createAjv } from '@jsonforms/core';
 import { Grid, Button, Alert } from '@mui/material';
 import logo from './logo.svg';
 import './App.css';
@@ -71,6 +72,12 @@ type UserState = {
   json_form: JsonFormsRequest;
 };
 
+// a constraint of the form schema broken by the submitted data
+type FormError = {
+  pointer: string;
+  message: string;
+};
+
 type InfoResponse = {
   response?: any;
   user_state?: UserState;
@@ -87,6 +94,10 @@ const renderers = [
   { tester: passwordControlTester, renderer: PasswordControl },
 ];
 
+// `$data` references let a field be checked against another, e.g. `must_equal`,
+// `next` adds the draft 2019 keywords like `dependentRequired`
+const ajv = createAjv({ $data: true, next: true });
+
 console.log(process.env);
 const AppEx = () => {
   const classes = useStyles();
@@ -101,6 +112,7 @@ const AppEx = () => {
   const [uibuttons, setUIButtons] = useState<Array<ButtonData>>([]);
   const [requestData, setRequestData] = useState<any>(null);
   const [apiError, setApiError] = useState<any>(null);
+  const [formErrors, setFormErrors] = useState<Array<any>>([]);
 
   function getApiMethod(name: string) {
     return Camelize(name).replace(/Request$/, '');
@@ -144,13 +156,33 @@ const AppEx = () => {
       const r: InfoResponse = response.data;
       setRequestData(null);
       setApiError(null);
+      setFormErrors([]);
       await handleApiResponse(r);
     }
   }
 
-  const handleApiError = useCallback((status: number, msg: string) => {
+  const handleApiError = useCallback((status: number, msg: any) => {
     console.log('ApiError:', status, msg);
-    if (status === StatusCode.Unauthorized) {
+    // invalid form data is a list of errors keyed by json pointer, shown on
+    // their field, errors of the whole form are listed below it
+    if (status === StatusCode.BadRequest && Array.isArray(msg)) {
+      const errors: Array<FormError> = msg;
+      setFormErrors(
+        errors
+          .filter((error) => error.pointer !== '')
+          .map((error) => ({
+            instancePath: error.pointer,
+            message: error.message,
+            schemaPath: '',
+            keyword: '',
+            params: {},
+          }))
+      );
+      const formLevel = errors.filter((error) => error.pointer === '');
+      setApiError(
+        formLevel.length > 0 ? formLevel.map((error) => error.message) : null
+      );
+    } else if (status === StatusCode.Unauthorized) {
       if (msg === 'InvalidToken') {
         onNextForm(LoginForm);
       } else setApiError(translateApiError(msg));
@@ -217,12 +249,13 @@ const AppEx = () => {
         const r: InfoResponse = response.data;
         if (r.response?.JsonForms) {
           const form = r.response.JsonForms;
-          setSchema(JSON.parse(form.schema));
-          setUISchema(JSON.parse(form.uischema));
+          setSchema(form.schema);
+          setUISchema(form.uischema);
           setUIButtons(form.buttons);
           setTitle(form.title);
           setRefreshForm(false);
           setApiError(null);
+          setFormErrors([]);
         }
       }
     }
@@ -286,6 +319,8 @@ const AppEx = () => {
               data={requestData}
               renderers={renderers}
               cells={materialCells}
+              ajv={ajv}
+              additionalErrors={formErrors}
               onChange={({ errors, data }) => setRequestData(data)}
             />
           </div>
@@ -303,7 +338,15 @@ const AppEx = () => {
         {apiError && (
           <Grid item xs={12}>
             <Alert severity='error' variant='filled'>
-              {apiError}
+              {Array.isArray(apiError) ? (
+                <ul>
+                  {apiError.map((message: string, i: number) => (
+                    <li key={`error_${i}`}>{message}</li>
+                  ))}
+                </ul>
+              ) : (
+                apiError
+              )}
             </Alert>
           </Grid>
         )}
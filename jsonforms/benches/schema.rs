@@ -0,0 +1,48 @@
+#![allow(dead_code)]
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use jsonforms::json_forms::*;
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+struct Cell {
+    #[jsonforms(min_length = 1)]
+    value: String,
+    #[jsonforms(minimum = 0)]
+    weight: f64,
+    note: Option<String>,
+}
+
+#[derive(JsonForms)]
+struct Row {
+    label: String,
+    cells: Vec<Cell>,
+}
+
+#[derive(JsonForms)]
+struct Table {
+    #[jsonforms(title = "Table Title")]
+    title: String,
+    rows: Vec<Row>,
+    footer: Vec<Cell>,
+    #[jsonforms(HorizontalLayout)]
+    visible: bool,
+    #[jsonforms(EndLayout)]
+    columns: i32,
+}
+
+fn schema_benchmark(c: &mut Criterion) {
+    c.bench_function("jsonforms_schema build", |b| {
+        b.iter(Table::jsonforms_schema)
+    });
+    c.bench_function("jsonforms_static cached", |b| {
+        b.iter(Table::jsonforms_static)
+    });
+    c.bench_function("jsonforms_value cached", |b| b.iter(Table::jsonforms_value));
+    c.bench_function("JsonFormsResponse cached", |b| {
+        b.iter(|| JsonFormsResponse::from(Table::jsonforms_static()))
+    });
+}
+
+criterion_group!(benches, schema_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,254 @@
+//! server side checks of the constraints a form schema declares on its fields,
+//! with the error messages of the ajv validator used by the client
+
+use crate::json_forms::{JsonFormsError, JsonFormsField};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// a field value as the json kinds the constraint keywords apply to, so the
+/// server checks it without serializing it
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonFormsValue<'a> {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(Cow<'a, str>),
+    Array(Vec<JsonFormsValue<'a>>),
+    /// objects and values without a view, they only pass their own checks
+    Other,
+}
+
+impl JsonFormsValue<'_> {
+    /// a scalar as its json value, other values have no view
+    pub fn scalar<T: Serialize + ?Sized>(value: &T) -> Self {
+        match serde_json::to_value(value) {
+            Ok(Value::Null) => Self::Null,
+            Ok(Value::Bool(b)) => Self::Bool(b),
+            Ok(Value::Number(n)) => Self::Number(n),
+            Ok(Value::String(s)) => Self::String(Cow::Owned(s)),
+            _ => Self::Other,
+        }
+    }
+
+    /// the json value, none if a part of it has no view
+    pub(crate) fn to_json(&self) -> Option<Value> {
+        Some(match self {
+            Self::Null => Value::Null,
+            Self::Bool(b) => Value::Bool(*b),
+            Self::Number(n) => Value::Number(n.clone()),
+            Self::String(s) => Value::String(s.to_string()),
+            Self::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(JsonFormsValue::to_json)
+                    .collect::<Option<_>>()?,
+            ),
+            Self::Other => return None,
+        })
+    }
+}
+
+/// checks `value` against the constraint keywords of the json object `schema`,
+/// the derive passes the ones declared on a field. Keywords only apply to
+/// values of their json type, like in a json schema, a value without a view
+/// never passes them
+pub fn jsonforms_check_value<T: JsonFormsField + ?Sized>(
+    value: &T,
+    schema: &str,
+    pointer: &str,
+    errors: &mut Vec<JsonFormsError>,
+) {
+    let Ok(schema) = serde_json::from_str::<Map<String, Value>>(schema) else {
+        errors.push(JsonFormsError::new(pointer, "must match the form schema"));
+        return;
+    };
+    let value = value.jsonforms_field_view();
+    for (keyword, limit) in &schema {
+        if let Some(message) = check_keyword(&value, keyword, limit) {
+            errors.push(JsonFormsError::new(pointer, message));
+        }
+    }
+}
+
+/// the error message if `value` breaks `keyword`
+fn check_keyword(value: &JsonFormsValue, keyword: &str, limit: &Value) -> Option<String> {
+    use JsonFormsValue as V;
+    let failed = match (keyword, value) {
+        ("minLength", V::String(s)) => as_len(limit).is_some_and(|n| s.chars().count() < n),
+        ("maxLength", V::String(s)) => as_len(limit).is_some_and(|n| s.chars().count() > n),
+        // a pattern the server can't compile never passes
+        ("pattern", V::String(s)) => limit
+            .as_str()
+            .and_then(pattern)
+            .is_none_or(|re| !re.is_match(s)),
+        ("format", V::String(s)) => limit.as_str().is_none_or(|format| !check_format(s, format)),
+        ("minimum", V::Number(n)) => compare(n, limit).is_some_and(|(v, l)| v < l),
+        ("maximum", V::Number(n)) => compare(n, limit).is_some_and(|(v, l)| v > l),
+        ("exclusiveMinimum", V::Number(n)) => compare(n, limit).is_some_and(|(v, l)| v <= l),
+        ("exclusiveMaximum", V::Number(n)) => compare(n, limit).is_some_and(|(v, l)| v >= l),
+        ("multipleOf", V::Number(n)) => compare(n, limit)
+            .is_some_and(|(v, l)| l > 0.0 && ((v / l) - (v / l).round()).abs() > f64::EPSILON),
+        ("minItems", V::Array(items)) => as_len(limit).is_some_and(|n| items.len() < n),
+        ("maxItems", V::Array(items)) => as_len(limit).is_some_and(|n| items.len() > n),
+        ("uniqueItems", V::Array(items)) => {
+            limit == &Value::Bool(true)
+                && items
+                    .iter()
+                    .map(JsonFormsValue::to_json)
+                    .collect::<Option<Vec<_>>>()
+                    .is_none_or(|items| {
+                        items
+                            .iter()
+                            .enumerate()
+                            .any(|(i, item)| items[..i].contains(item))
+                    })
+        }
+        ("enum", value) => match (value.to_json(), limit.as_array()) {
+            (Some(value), Some(allowed)) => !allowed.contains(&value),
+            _ => true,
+        },
+        ("const", value) => value.to_json().as_ref() != Some(limit),
+        (_, V::Other) => true,
+        _ => false,
+    };
+    if !failed {
+        return None;
+    }
+    Some(match keyword {
+        "minLength" => format!("must NOT have fewer than {} characters", limit),
+        "maxLength" => format!("must NOT have more than {} characters", limit),
+        "pattern" => format!("must match pattern {}", limit),
+        "format" => format!("must match format {}", limit),
+        "minimum" => format!("must be >= {}", limit),
+        "maximum" => format!("must be <= {}", limit),
+        "exclusiveMinimum" => format!("must be > {}", limit),
+        "exclusiveMaximum" => format!("must be < {}", limit),
+        "multipleOf" => format!("must be multiple of {}", limit),
+        "minItems" => format!("must NOT have fewer than {} items", limit),
+        "maxItems" => format!("must NOT have more than {} items", limit),
+        "uniqueItems" => "must NOT have duplicate items".to_owned(),
+        "enum" => "must be equal to one of the allowed values".to_owned(),
+        _ => "must be equal to constant".to_owned(),
+    })
+}
+
+/// the formats of ajv-formats the derive lets through, checked like its
+/// `full` mode. Other formats never pass
+fn check_format(s: &str, format: &str) -> bool {
+    let matches = |re: &str| pattern(re).is_some_and(|re| re.is_match(s));
+    match format {
+        "date" => is_date(s),
+        "time" => is_time(s, false),
+        "date-time" => s
+            .split_once(['T', 't', ' '])
+            .is_some_and(|(date, time)| is_date(date) && is_time(time, true)),
+        "email" => matches(
+            r"(?i)^[a-z0-9!#$%&'*+/=?^_`{|}~-]+(?:\.[a-z0-9!#$%&'*+/=?^_`{|}~-]+)*@(?:[a-z0-9](?:[a-z0-9-]*[a-z0-9])?\.)+[a-z0-9](?:[a-z0-9-]*[a-z0-9])?$",
+        ),
+        "uri" => matches(r"(?i)^[a-z][a-z0-9+\-.]*:[^\s]*$"),
+        "uri-reference" => matches(
+            r"(?i)^(?:(?:[a-z][a-z0-9+\-.]*:)?/?/)?(?:[^\\\s#][^\s#]*)?(?:#[^\\\s]*)?$",
+        ),
+        "uuid" => matches(r"(?i)^(?:urn:uuid:)?[0-9a-f]{8}-(?:[0-9a-f]{4}-){3}[0-9a-f]{12}$"),
+        "ipv4" => s.parse::<std::net::Ipv4Addr>().is_ok(),
+        "ipv6" => s.parse::<std::net::Ipv6Addr>().is_ok(),
+        "hostname" => {
+            s.trim_end_matches('.').len() <= 253
+                && matches(
+                    r"(?i)^[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?(?:\.[a-z0-9](?:[-0-9a-z]{0,61}[0-9a-z])?)*\.?$",
+                )
+        }
+        // a rendering hint of json forms
+        "password" => true,
+        _ => false,
+    }
+}
+
+/// `yyyy-mm-dd`, a day of the month
+fn is_date(s: &str) -> bool {
+    let Some(re) = pattern(r"^(\d{4})-(\d{2})-(\d{2})$") else {
+        return false;
+    };
+    let Some(caps) = re.captures(s) else {
+        return false;
+    };
+    let n = |i: usize| caps[i].parse::<u32>().unwrap_or(0);
+    let (year, month, day) = (n(1), n(2), n(3));
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    (1..=12).contains(&month) && (1..=days).contains(&day)
+}
+
+/// `hh:mm:ss` with an optional fraction and time zone, a leap second is
+/// only valid at 23:59 utc
+fn is_time(s: &str, needs_zone: bool) -> bool {
+    let Some(re) = pattern(r"(?i)^(\d\d):(\d\d):(\d\d(?:\.\d+)?)(z|([+-])(\d\d)(?::?(\d\d))?)?$")
+    else {
+        return false;
+    };
+    let Some(caps) = re.captures(s) else {
+        return false;
+    };
+    let n = |i: usize| {
+        caps.get(i)
+            .map_or(0, |m| m.as_str().parse::<i32>().unwrap_or(0))
+    };
+    let (hour, min, zone_hour, zone_min) = (n(1), n(2), n(6), n(7));
+    let sec = caps[3].parse::<f64>().unwrap_or(0.0);
+    if zone_hour > 23 || zone_min > 59 || (needs_zone && caps.get(4).is_none()) {
+        return false;
+    }
+    if hour <= 23 && min <= 59 && sec < 60.0 {
+        return true;
+    }
+    let sign = if caps.get(5).is_some_and(|m| m.as_str() == "-") {
+        -1
+    } else {
+        1
+    };
+    let utc_min = min - zone_min * sign;
+    let utc_hour = hour - zone_hour * sign - i32::from(utc_min < 0);
+    (utc_hour == 23 || utc_hour == -1) && (utc_min == 59 || utc_min == -1) && sec < 61.0
+}
+
+fn as_len(limit: &Value) -> Option<usize> {
+    limit.as_u64().and_then(|n| usize::try_from(n).ok())
+}
+
+fn compare(value: &Number, limit: &Value) -> Option<(f64, f64)> {
+    Some((value.as_f64()?, limit.as_f64()?))
+}
+
+/// compiled patterns, an invalid pattern checks nothing
+fn pattern(pattern: &str) -> Option<Regex> {
+    static PATTERNS: OnceLock<RwLock<HashMap<String, Option<Regex>>>> = OnceLock::new();
+    let patterns = PATTERNS.get_or_init(Default::default);
+    if let Some(re) = patterns.read().unwrap().get(pattern) {
+        return re.clone();
+    }
+    let re = Regex::new(pattern).ok();
+    patterns
+        .write()
+        .unwrap()
+        .insert(pattern.to_owned(), re.clone());
+    re
+}
+
+/// a map key as a json pointer reference token, keys are strings in json
+pub fn jsonforms_key<K: Serialize + ?Sized>(key: &K) -> String {
+    let key = match serde_json::to_value(key) {
+        Ok(Value::String(key)) => key,
+        Ok(key) => key.to_string(),
+        Err(_) => String::new(),
+    };
+    key.replace('~', "~0").replace('/', "~1")
+}
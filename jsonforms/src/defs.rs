@@ -0,0 +1,174 @@
+//! shared `$defs` of a form schema, types referenced by the fields are defined
+//! once and pointed to with `$ref`, which also makes recursive types possible
+
+use crate::json_forms::{json_value, JsonFormsSerializable};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+
+/// definitions collected while building the schema of one form
+#[derive(Default)]
+pub struct JsonFormsDefs {
+    /// definition name of each type, by `std::any::type_name`
+    names: HashMap<&'static str, String>,
+    /// definition schemas by name, empty while the definition is being built
+    defs: BTreeMap<String, String>,
+}
+
+impl JsonFormsDefs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `$ref` to the definition of `T`, built on first use
+    pub fn reference<T: JsonFormsSerializable + ?Sized>(&mut self) -> String {
+        let type_name = std::any::type_name::<T>();
+        let name = match self.names.get(type_name) {
+            Some(name) => name.clone(),
+            None => {
+                let name = self.unique_name(type_name);
+                self.names.insert(type_name, name.clone());
+                self.defs.insert(name.clone(), String::new());
+                let schema = T::jsonforms_definition(self);
+                self.defs.insert(name.clone(), schema);
+                name
+            }
+        };
+        format!(r##"{{"$ref":"#/$defs/{}"}}"##, name)
+    }
+
+    /// the `root` schema with the definitions it uses. Definitions referenced
+    /// once and not recursive are put back inline, so `$defs` only holds
+    /// repeated and recursive types
+    pub fn schema(self, root: String) -> String {
+        if self.defs.is_empty() {
+            return root;
+        }
+        let mut root = json_value(root);
+        let mut defs: BTreeMap<String, Value> = self
+            .defs
+            .into_iter()
+            .map(|(name, schema)| (name, json_value(schema)))
+            .collect();
+        loop {
+            let mut counts = HashMap::new();
+            count_refs(&root, &mut counts);
+            defs.values().for_each(|def| count_refs(def, &mut counts));
+            let Some(name) = defs
+                .keys()
+                .find(|name| counts.get(*name) == Some(&1) && !is_recursive(&defs, name))
+                .cloned()
+            else {
+                break;
+            };
+            let def = defs.remove(&name).unwrap_or_default();
+            let target = def_ref(&name);
+            if !inline_ref(&mut root, &target, &def) {
+                for other in defs.values_mut() {
+                    if inline_ref(other, &target, &def) {
+                        break;
+                    }
+                }
+            }
+        }
+        if let (Some(obj), false) = (root.as_object_mut(), defs.is_empty()) {
+            obj.insert(
+                "$defs".to_owned(),
+                Value::Object(defs.into_iter().collect()),
+            );
+        }
+        root.to_string()
+    }
+
+    /// `Page<Address>` is named `Page_Address`, without module paths.
+    /// Types with the same name in different modules get a number
+    fn unique_name(&self, type_name: &str) -> String {
+        let mut name = String::new();
+        let mut segment = 0;
+        for ch in type_name.chars() {
+            if ch.is_alphanumeric() || ch == '_' {
+                name.push(ch);
+            } else if ch == ':' {
+                name.truncate(segment);
+            } else if !name.is_empty() && !name.ends_with('_') {
+                name.push('_');
+                segment = name.len();
+            } else {
+                segment = name.len();
+            }
+        }
+        let name = name.trim_end_matches('_').to_owned();
+        let mut unique = name.clone();
+        let mut n = 1;
+        while self.defs.contains_key(&unique) {
+            n += 1;
+            unique = format!("{}{}", name, n);
+        }
+        unique
+    }
+}
+
+fn def_ref(name: &str) -> String {
+    format!("#/$defs/{}", name)
+}
+
+fn ref_name(value: &Map<String, Value>) -> Option<&str> {
+    value.get("$ref")?.as_str()?.strip_prefix("#/$defs/")
+}
+
+/// number of `$ref` to each definition
+fn count_refs(value: &Value, counts: &mut HashMap<String, usize>) {
+    match value {
+        Value::Object(obj) => {
+            if let Some(name) = ref_name(obj) {
+                *counts.entry(name.to_owned()).or_default() += 1;
+            }
+            obj.values().for_each(|v| count_refs(v, counts));
+        }
+        Value::Array(arr) => arr.iter().for_each(|v| count_refs(v, counts)),
+        _ => (),
+    }
+}
+
+/// true if the definition `name` references itself, directly or not
+fn is_recursive(defs: &BTreeMap<String, Value>, name: &str) -> bool {
+    let mut todo = vec![name.to_owned()];
+    let mut seen = Vec::new();
+    while let Some(next) = todo.pop() {
+        let Some(def) = defs.get(&next) else {
+            continue;
+        };
+        let mut counts = HashMap::new();
+        count_refs(def, &mut counts);
+        for used in counts.into_keys() {
+            if used == name {
+                return true;
+            }
+            if !seen.contains(&used) {
+                seen.push(used.clone());
+                todo.push(used);
+            }
+        }
+    }
+    false
+}
+
+/// replaces the first `$ref` to `target` by `def`, members next to the
+/// `$ref` (e.g. a field title) are kept over the definition ones
+fn inline_ref(value: &mut Value, target: &str, def: &Value) -> bool {
+    match value {
+        Value::Object(obj) => {
+            if obj.get("$ref").and_then(Value::as_str) == Some(target) {
+                let mut inlined = def.clone();
+                if let Some(inlined_obj) = inlined.as_object_mut() {
+                    obj.remove("$ref");
+                    inlined_obj.extend(std::mem::take(obj));
+                }
+                *value = inlined;
+                return true;
+            }
+            obj.values_mut().any(|v| inline_ref(v, target, def))
+        }
+        Value::Array(arr) => arr.iter_mut().any(|v| inline_ref(v, target, def)),
+        _ => false,
+    }
+}
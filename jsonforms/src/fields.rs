@@ -0,0 +1,309 @@
+//! JsonFormsField for std types, the same schemas the derive inlines for them
+
+use crate::json_forms::{
+    jsonforms_key, JsonFormsDefs, JsonFormsError, JsonFormsField, JsonFormsSerializable,
+    JsonFormsValue,
+};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+/// `$ty => $schema, |value| view`, the view is the json text serde gives
+macro_rules! field_schema {
+    ($($ty:ty => $schema:literal, |$value:ident| $view:expr),* $(,)?) => {
+        $(impl JsonFormsField for $ty {
+            fn jsonforms_field_schema(_defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+                Cow::Borrowed($schema)
+            }
+
+            fn jsonforms_field_view(&self) -> JsonFormsValue<'_> {
+                let $value = self;
+                $view
+            }
+        })*
+    };
+}
+
+field_schema! {
+    String => r#"{"type":"string"}"#, |s| JsonFormsValue::String(Cow::Borrowed(s)),
+    char => r#"{"type":"string","minLength":1,"maxLength":1}"#,
+        |c| JsonFormsValue::String(Cow::Owned(c.to_string())),
+    bool => r#"{"type":"boolean"}"#, |b| JsonFormsValue::Bool(*b),
+    f32 => r#"{"type":"number"}"#, |n| JsonFormsValue::scalar(n),
+    f64 => r#"{"type":"number"}"#, |n| JsonFormsValue::scalar(n),
+}
+
+#[cfg(feature = "chrono")]
+field_schema! {
+    chrono::NaiveDate => r#"{"type":"string","format":"date"}"#,
+        |date| JsonFormsValue::String(Cow::Owned(date.to_string())),
+    chrono::NaiveTime => r#"{"type":"string","format":"time"}"#,
+        |time| JsonFormsValue::String(Cow::Owned(time.to_string())),
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> JsonFormsField for chrono::DateTime<Tz> {
+    fn jsonforms_field_schema(_defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+        Cow::Borrowed(r#"{"type":"string","format":"date-time"}"#)
+    }
+
+    /// the rfc 3339 text of serde, `2024-01-31T08:00:00Z`
+    fn jsonforms_field_view(&self) -> JsonFormsValue<'_> {
+        JsonFormsValue::String(Cow::Owned(format!("{:?}", self)))
+    }
+}
+
+#[cfg(feature = "uuid")]
+field_schema! {
+    uuid::Uuid => r#"{"type":"string","format":"uuid"}"#,
+        |uuid| JsonFormsValue::String(Cow::Owned(uuid.to_string())),
+}
+
+#[cfg(feature = "url")]
+field_schema! {
+    url::Url => r#"{"type":"string","format":"uri"}"#,
+        |url| JsonFormsValue::String(Cow::Borrowed(url.as_str())),
+}
+
+/// integers are bounded by their type range
+macro_rules! int_schema {
+    ($($ty:ty),*) => {
+        $(impl JsonFormsField for $ty {
+            fn jsonforms_field_schema(_defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+                Cow::Owned(format!(
+                    r#"{{"type":"integer","minimum":{},"maximum":{}}}"#,
+                    <$ty>::MIN,
+                    <$ty>::MAX
+                ))
+            }
+
+            fn jsonforms_field_view(&self) -> JsonFormsValue<'_> {
+                JsonFormsValue::scalar(self)
+            }
+        })*
+    };
+}
+
+int_schema!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! non_zero_schema {
+    ($($ty:ty => $prim:ty),*) => {
+        $(impl JsonFormsField for $ty {
+            fn jsonforms_field_schema(_defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+                Cow::Owned(if <$prim>::MIN == 0 {
+                    format!(r#"{{"type":"integer","minimum":1,"maximum":{}}}"#, <$prim>::MAX)
+                } else {
+                    format!(
+                        r#"{{"type":"integer","minimum":{},"maximum":{},"not":{{"const":0}}}}"#,
+                        <$prim>::MIN,
+                        <$prim>::MAX
+                    )
+                })
+            }
+
+            fn jsonforms_field_view(&self) -> JsonFormsValue<'_> {
+                JsonFormsValue::scalar(self)
+            }
+        })*
+    };
+}
+
+non_zero_schema!(
+    NonZeroI8 => i8, NonZeroI16 => i16, NonZeroI32 => i32, NonZeroI64 => i64,
+    NonZeroI128 => i128, NonZeroIsize => isize, NonZeroU8 => u8, NonZeroU16 => u16,
+    NonZeroU32 => u32, NonZeroU64 => u64, NonZeroU128 => u128, NonZeroUsize => usize
+);
+
+/// a boxed value is the value, a Box can't implement JsonFormsField itself
+/// next to the blanket implementation. The derive inlines the Box of any type
+impl<T: JsonFormsSerializable> JsonFormsSerializable for Box<T> {
+    fn jsonforms_schema() -> (String, String) {
+        T::jsonforms_schema()
+    }
+
+    fn jsonforms_definition(defs: &mut JsonFormsDefs) -> String {
+        defs.reference::<T>()
+    }
+
+    const JSONFORMS_LAYOUT: bool = T::JSONFORMS_LAYOUT;
+
+    fn jsonforms_uischema(scope: &str, extra: &str) -> String {
+        T::jsonforms_uischema(scope, extra)
+    }
+
+    fn jsonforms_check(&self, pointer: &str, errors: &mut Vec<JsonFormsError>) {
+        T::jsonforms_check(self, pointer, errors);
+    }
+
+    fn jsonforms_view(&self) -> JsonFormsValue<'_> {
+        T::jsonforms_view(self)
+    }
+}
+
+/// an optional field has the schema and uischema of its value
+impl<T: JsonFormsField> JsonFormsField for Option<T> {
+    const JSONFORMS_LAYOUT: bool = T::JSONFORMS_LAYOUT;
+
+    fn jsonforms_field_schema(defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+        T::jsonforms_field_schema(defs)
+    }
+
+    fn jsonforms_field_uischema(scope: &str, extra: &str) -> String {
+        T::jsonforms_field_uischema(scope, extra)
+    }
+
+    fn jsonforms_field_check(&self, pointer: &str, errors: &mut Vec<JsonFormsError>) {
+        if let Some(value) = self {
+            value.jsonforms_field_check(pointer, errors);
+        }
+    }
+
+    fn jsonforms_field_view(&self) -> JsonFormsValue<'_> {
+        match self {
+            Some(value) => value.jsonforms_field_view(),
+            None => JsonFormsValue::Null,
+        }
+    }
+}
+
+fn array_schema<T: JsonFormsField>(defs: &mut JsonFormsDefs, head: &str) -> Cow<'static, str> {
+    Cow::Owned(format!(
+        r#"{{{}"items":{}}}"#,
+        head,
+        T::jsonforms_field_schema(defs)
+    ))
+}
+
+/// items are found at their index below the array
+fn check_items<'a, T: JsonFormsField + 'a>(
+    items: impl Iterator<Item = &'a T>,
+    pointer: &str,
+    errors: &mut Vec<JsonFormsError>,
+) {
+    for (i, item) in items.enumerate() {
+        item.jsonforms_field_check(&format!("{}/{}", pointer, i), errors);
+    }
+}
+
+fn items_view<'a, T: JsonFormsField + 'a>(
+    items: impl Iterator<Item = &'a T>,
+) -> JsonFormsValue<'a> {
+    JsonFormsValue::Array(items.map(T::jsonforms_field_view).collect())
+}
+
+impl<T: JsonFormsField> JsonFormsField for Vec<T> {
+    fn jsonforms_field_schema(defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+        array_schema::<T>(defs, r#""type":"array","#)
+    }
+
+    fn jsonforms_field_check(&self, pointer: &str, errors: &mut Vec<JsonFormsError>) {
+        check_items(self.iter(), pointer, errors);
+    }
+
+    fn jsonforms_field_view(&self) -> JsonFormsValue<'_> {
+        items_view(self.iter())
+    }
+}
+
+impl<T: JsonFormsField> JsonFormsField for VecDeque<T> {
+    fn jsonforms_field_schema(defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+        array_schema::<T>(defs, r#""type":"array","#)
+    }
+
+    fn jsonforms_field_check(&self, pointer: &str, errors: &mut Vec<JsonFormsError>) {
+        check_items(self.iter(), pointer, errors);
+    }
+
+    fn jsonforms_field_view(&self) -> JsonFormsValue<'_> {
+        items_view(self.iter())
+    }
+}
+
+impl<T: JsonFormsField, S> JsonFormsField for HashSet<T, S> {
+    fn jsonforms_field_schema(defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+        array_schema::<T>(defs, r#""type":"array","uniqueItems":true,"#)
+    }
+
+    fn jsonforms_field_check(&self, pointer: &str, errors: &mut Vec<JsonFormsError>) {
+        check_items(self.iter(), pointer, errors);
+    }
+
+    fn jsonforms_field_view(&self) -> JsonFormsValue<'_> {
+        items_view(self.iter())
+    }
+}
+
+impl<T: JsonFormsField> JsonFormsField for BTreeSet<T> {
+    fn jsonforms_field_schema(defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+        array_schema::<T>(defs, r#""type":"array","uniqueItems":true,"#)
+    }
+
+    fn jsonforms_field_check(&self, pointer: &str, errors: &mut Vec<JsonFormsError>) {
+        check_items(self.iter(), pointer, errors);
+    }
+
+    fn jsonforms_field_view(&self) -> JsonFormsValue<'_> {
+        items_view(self.iter())
+    }
+}
+
+/// fixed size arrays have exactly `N` items
+impl<T: JsonFormsField, const N: usize> JsonFormsField for [T; N] {
+    fn jsonforms_field_schema(defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+        array_schema::<T>(
+            defs,
+            &format!(r#""type":"array","minItems":{},"maxItems":{},"#, N, N),
+        )
+    }
+
+    fn jsonforms_field_check(&self, pointer: &str, errors: &mut Vec<JsonFormsError>) {
+        check_items(self.iter(), pointer, errors);
+    }
+
+    fn jsonforms_field_view(&self) -> JsonFormsValue<'_> {
+        items_view(self.iter())
+    }
+}
+
+/// keys are strings in json, values follow the value type and are found at
+/// their key below the map
+fn map_schema<V: JsonFormsField>(defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+    Cow::Owned(format!(
+        r#"{{"type":"object","additionalProperties":{}}}"#,
+        V::jsonforms_field_schema(defs)
+    ))
+}
+
+fn check_values<'a, K: Serialize + 'a, V: JsonFormsField + 'a>(
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+    pointer: &str,
+    errors: &mut Vec<JsonFormsError>,
+) {
+    for (key, value) in entries {
+        value.jsonforms_field_check(&format!("{}/{}", pointer, jsonforms_key(key)), errors);
+    }
+}
+
+impl<K: Serialize, V: JsonFormsField, S> JsonFormsField for HashMap<K, V, S> {
+    fn jsonforms_field_schema(defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+        map_schema::<V>(defs)
+    }
+
+    fn jsonforms_field_check(&self, pointer: &str, errors: &mut Vec<JsonFormsError>) {
+        check_values(self.iter(), pointer, errors);
+    }
+}
+
+impl<K: Serialize, V: JsonFormsField> JsonFormsField for BTreeMap<K, V> {
+    fn jsonforms_field_schema(defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+        map_schema::<V>(defs)
+    }
+
+    fn jsonforms_field_check(&self, pointer: &str, errors: &mut Vec<JsonFormsError>) {
+        check_values(self.iter(), pointer, errors);
+    }
+}
@@ -1,4 +1,12 @@
 use serde::{Deserialize, Serialize};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+pub use crate::check::{jsonforms_check_value, jsonforms_key, JsonFormsValue};
+pub use crate::defs::JsonFormsDefs;
 
 #[macro_export]
 macro_rules! stringify_nosp {
@@ -37,8 +45,8 @@ pub struct Button {
 
 #[derive(Serialize,Debug)]
 pub struct JsonFormsResponse {
-    pub schema: String,
-    pub uischema: String,
+    pub schema: Cow<'static, serde_json::Value>,
+    pub uischema: Cow<'static, serde_json::Value>,
     pub buttons: Vec<Button>,
     pub title: Option<String>,
 }
@@ -49,19 +57,351 @@ impl JsonFormsResponse {
     }
 }
 
+impl From<(serde_json::Value, serde_json::Value)> for JsonFormsResponse {
+    fn from((schema, uischema): (serde_json::Value, serde_json::Value)) -> Self {
+        Self {
+            title: schema_title(&schema),
+            schema: Cow::Owned(schema),
+            uischema: Cow::Owned(uischema),
+            buttons: Vec::new(),
+        }
+    }
+}
+
+impl From<&'static JsonFormsSchema> for JsonFormsResponse {
+    fn from(schema: &'static JsonFormsSchema) -> Self {
+        Self {
+            schema: Cow::Borrowed(&schema.schema_value),
+            uischema: Cow::Borrowed(&schema.uischema_value),
+            buttons: Vec::new(),
+            title: schema_title(&schema.schema_value),
+        }
+    }
+}
+
 impl From<(String, String)> for JsonFormsResponse {
     fn from((schema, uischema): (String, String)) -> Self {
+        Self::from((json_value(schema), json_value(uischema)))
+    }
+}
+
+/// the form title defaults to the title of its schema
+fn schema_title(schema: &serde_json::Value) -> Option<String> {
+    schema["title"].as_str().map(str::to_owned)
+}
+
+/// invalid json is kept as a json string, so it still reaches the client
+pub(crate) fn json_value(s: String) -> serde_json::Value {
+    serde_json::from_str(&s).unwrap_or(serde_json::Value::String(s))
+}
+
+/// schema and uischema of a type, built once and kept for the program lifetime
+#[derive(Debug)]
+pub struct JsonFormsSchema {
+    pub schema: String,
+    pub uischema: String,
+    pub schema_value: serde_json::Value,
+    pub uischema_value: serde_json::Value,
+}
+
+impl JsonFormsSchema {
+    pub fn new((schema, uischema): (String, String)) -> Self {
         Self {
+            schema_value: json_value(schema.clone()),
+            uischema_value: json_value(uischema.clone()),
             schema,
             uischema,
-            buttons: Vec::new(),
-            title: None,
         }
     }
 }
 
 pub trait JsonFormsSerializable {
+    /// builds the schema and uischema, prefer the cached `jsonforms_static`
     fn jsonforms_schema() -> (String, String);
+
+    /// schema of the type inside the schema of a form, the types it references
+    /// are added to `defs`. Only the derive uses `defs`, other implementations
+    /// give their whole schema
+    fn jsonforms_definition(_defs: &mut JsonFormsDefs) -> String {
+        Self::jsonforms_schema().0
+    }
+
+    /// schema and uischema built on first use, the derive keeps them in a static
+    /// per type, other implementations share a cache keyed by type
+    fn jsonforms_static() -> &'static JsonFormsSchema
+    where
+        Self: Sized + 'static,
+    {
+        static CACHE: OnceLock<RwLock<HashMap<TypeId, &'static JsonFormsSchema>>> = OnceLock::new();
+        let cache = CACHE.get_or_init(Default::default);
+        if let Some(schema) = cache.read().unwrap().get(&TypeId::of::<Self>()) {
+            return schema;
+        }
+        // built outside the lock, nested types fill the cache too
+        let schema = JsonFormsSchema::new(Self::jsonforms_schema());
+        cache
+            .write()
+            .unwrap()
+            .entry(TypeId::of::<Self>())
+            .or_insert_with(|| Box::leak(Box::new(schema)))
+    }
+
+    /// schema and uischema as json values, ready to be embedded in a response
+    fn jsonforms_value() -> (serde_json::Value, serde_json::Value)
+    where
+        Self: Sized + 'static,
+    {
+        let schema = Self::jsonforms_static();
+        (schema.schema_value.clone(), schema.uischema_value.clone())
+    }
+
+    /// true if `jsonforms_uischema` is a layout, the derive sets it for
+    /// structs with named fields
+    const JSONFORMS_LAYOUT: bool = false;
+
+    /// uischema element of a field with this type, found at `scope`.
+    /// `extra` are the field raw uischema members, comma separated
+    fn jsonforms_uischema(scope: &str, extra: &str) -> String {
+        jsonforms_control(scope, extra)
+    }
+
+    /// adds the constraints of the schema broken by `self` to `errors`,
+    /// `pointer` is where `self` is in the form data. The derive checks the
+    /// constraints of the fields and the nested values, other implementations
+    /// accept any value
+    fn jsonforms_check(&self, _pointer: &str, _errors: &mut Vec<JsonFormsError>) {}
+
+    /// the value as seen by the constraint keywords of the fields using the
+    /// type, the derive gives the one of the newtype field and the names of
+    /// unit variants
+    fn jsonforms_view(&self) -> JsonFormsValue<'_> {
+        JsonFormsValue::Other
+    }
+
+    /// checks submitted form data against the constraints of its schema
+    fn validate(&self) -> Result<(), Vec<JsonFormsError>> {
+        let mut errors = Vec::new();
+        self.jsonforms_check("", &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// a constraint of the form schema broken by the submitted data
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct JsonFormsError {
+    /// json pointer to the value in the form data, empty for the whole form
+    pub pointer: String,
+    pub message: String,
+}
+
+impl JsonFormsError {
+    pub fn new(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            pointer: pointer.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for JsonFormsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.pointer.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{} {}", self.pointer, self.message)
+        }
+    }
+}
+
+/// schema of a field type, used by the derive for the types it does not inline.
+/// Implemented for std types and every JsonFormsSerializable type, newtypes
+/// like an `Email` string implement it to declare their own schema
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no jsonforms schema",
+    label = "field type not supported by JsonForms",
+    note = "derive JsonForms for it or implement JsonFormsField"
+)]
+pub trait JsonFormsField {
+    /// json schema of the field value, types shared by several fields go to `defs`
+    fn jsonforms_field_schema(defs: &mut JsonFormsDefs) -> Cow<'static, str>;
+
+    /// true if the field uischema is a layout, arrays of such items render
+    /// them with it as their `detail`
+    const JSONFORMS_LAYOUT: bool = false;
+
+    /// uischema element of the field found at `scope`, a Control by default
+    fn jsonforms_field_uischema(scope: &str, extra: &str) -> String {
+        jsonforms_control(scope, extra)
+    }
+
+    /// adds the constraints broken by the field value found at `pointer` to
+    /// `errors`, std collections check their items
+    fn jsonforms_field_check(&self, _pointer: &str, _errors: &mut Vec<JsonFormsError>) {}
+
+    /// the field value as seen by the constraint keywords, a value without
+    /// a view fails them
+    fn jsonforms_field_view(&self) -> JsonFormsValue<'_> {
+        JsonFormsValue::Other
+    }
+}
+
+impl<T: JsonFormsSerializable> JsonFormsField for T {
+    const JSONFORMS_LAYOUT: bool = T::JSONFORMS_LAYOUT;
+
+    fn jsonforms_field_schema(defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+        Cow::Owned(defs.reference::<T>())
+    }
+
+    fn jsonforms_field_uischema(scope: &str, extra: &str) -> String {
+        T::jsonforms_uischema(scope, extra)
+    }
+
+    fn jsonforms_field_check(&self, pointer: &str, errors: &mut Vec<JsonFormsError>) {
+        self.jsonforms_check(pointer, errors)
+    }
+
+    fn jsonforms_field_view(&self) -> JsonFormsValue<'_> {
+        self.jsonforms_view()
+    }
+}
+
+/// a Control element at `scope`, with the raw uischema members `extra`
+pub fn jsonforms_control(scope: &str, extra: &str) -> String {
+    let mut uiout_str = format!(r#"{{"type":"Control","scope":"{}""#, scope);
+    if !extra.is_empty() {
+        uiout_str += ",";
+        uiout_str += extra;
+    }
+    uiout_str += "}";
+    uiout_str
+}
+
+/// the uischema built by `group` for a field of type `T`, or a Control if `T`
+/// is already being built higher up, so recursive types don't nest forever
+pub fn jsonforms_group<T: ?Sized>(
+    scope: &str,
+    extra: &str,
+    group: impl FnOnce() -> String,
+) -> String {
+    thread_local! {
+        static GROUPS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    }
+    let name = std::any::type_name::<T>();
+    if GROUPS.with(|groups| groups.borrow().contains(&name)) {
+        return jsonforms_control(scope, extra);
+    }
+    GROUPS.with(|groups| groups.borrow_mut().push(name));
+    let uischema = group();
+    GROUPS.with(|groups| groups.borrow_mut().pop());
+    uischema
+}
+
+/// merges the `properties`, `required`, `dependentRequired` and `allOf` of the
+/// `inner` object schema into `schema`, used by the derive for
+/// `#[serde(flatten)]` fields. The constraints of an `optional` field are
+/// left out, its properties may all be missing
+pub fn jsonforms_flatten(schema: &mut String, inner: &str, optional: bool) {
+    let (Ok(mut outer), Ok(inner)) = (
+        serde_json::from_str::<serde_json::Value>(schema),
+        serde_json::from_str::<serde_json::Value>(inner),
+    ) else {
+        return;
+    };
+    let Some(outer_obj) = outer.as_object_mut() else {
+        return;
+    };
+    if let Some(inner_props) = inner["properties"].as_object() {
+        if let Some(props) = outer_obj
+            .entry("properties")
+            .or_insert_with(|| serde_json::json!({}))
+            .as_object_mut()
+        {
+            props.extend(inner_props.clone());
+        }
+    }
+    if optional {
+        *schema = outer.to_string();
+        return;
+    }
+    if let Some(inner_dependent) = inner["dependentRequired"].as_object() {
+        if let Some(dependent) = outer_obj
+            .entry("dependentRequired")
+            .or_insert_with(|| serde_json::json!({}))
+            .as_object_mut()
+        {
+            dependent.extend(inner_dependent.clone());
+        }
+    }
+    for key in ["required", "allOf"] {
+        if let Some(inner_list) = inner[key].as_array() {
+            if let Some(list) = outer_obj
+                .entry(key)
+                .or_insert_with(|| serde_json::json!([]))
+                .as_array_mut()
+            {
+                list.extend(inner_list.iter().cloned());
+            }
+        }
+    }
+    *schema = outer.to_string();
+}
+
+/// appends the `schema` object to `out`, with the raw json object `members`
+/// replacing its own, used by the derive for attributes of referenced types
+pub fn jsonforms_extend(out: &mut String, schema: &str, members: &str) {
+    let mut value = json_value(schema.to_owned());
+    if let (Some(obj), Ok(members)) = (
+        value.as_object_mut(),
+        serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&format!(
+            "{{{}}}",
+            members
+        )),
+    ) {
+        obj.extend(members);
+    }
+    *out += &value.to_string();
+}
+
+/// merges the `inner` object schema of an internally tagged newtype variant
+/// into the variant `schema`, like a flattened field. The tag is one of the
+/// `properties`, so the `additionalProperties` of the inner type still hold
+pub fn jsonforms_tagged(schema: &mut String, inner: &str) {
+    jsonforms_flatten(schema, inner, false);
+    let inner = json_value(inner.to_owned());
+    let Some(additional) = inner.get("additionalProperties") else {
+        return;
+    };
+    let mut outer = json_value(std::mem::take(schema));
+    if let Some(obj) = outer.as_object_mut() {
+        obj.insert("additionalProperties".to_owned(), additional.clone());
+    }
+    *schema = outer.to_string();
+}
+
+/// `schema` of a field with the serialized `value` as its default, used by
+/// the derive for `#[jsonforms(default)]` structs. A `default` attribute of
+/// the field is kept, `None` values give no default
+pub fn jsonforms_default<T: Serialize + ?Sized>(schema: String, value: &T) -> String {
+    let default = match serde_json::to_value(value) {
+        Ok(serde_json::Value::Null) | Err(_) => return schema,
+        Ok(default) => default,
+    };
+    let mut schema = json_value(schema);
+    if let Some(obj) = schema.as_object_mut() {
+        obj.entry("default").or_insert(default);
+    }
+    schema.to_string()
+}
+
+/// true if the view of `value` is the json `expected`, used by the derive to
+/// check `equals` conditions on the server
+pub fn jsonforms_equals<T: JsonFormsField + ?Sized>(value: &T, expected: &str) -> bool {
+    let expected = serde_json::from_str(expected).ok();
+    expected.is_some() && value.jsonforms_field_view().to_json() == expected
 }
 
 pub trait JsonFormsButtons {
@@ -117,9 +457,1746 @@ mod tests {
         #[jsonforms(schema = r#""enum": ["Enum Str 1","Enum Str 2","Enum Str 3"]"#)]
         some_enum: String,
 
+        #[jsonforms(EndLayout)]
         some_opt_str: Option<String>,
     }
 
+    #[derive(JsonForms, Serialize, Deserialize, Debug)]
+    #[serde(rename_all = "snake_case")]
+    enum Recurrence {
+        Never,
+        EveryDay,
+        #[serde(rename = "weekly")]
+        Weekly,
+        #[serde(skip)]
+        Hidden,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    enum Contact {
+        Unknown,
+        Email(String),
+        Phone { prefix: Option<i32>, number: String },
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[serde(tag = "kind")]
+    enum Shape {
+        Empty,
+        Circle { radius: f64 },
+        Square(Square),
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[serde(deny_unknown_fields)]
+    struct Square {
+        #[jsonforms(minimum = 0)]
+        side: f64,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsEnum {
+        recurrence: Recurrence,
+        contact: Option<Contact>,
+        #[jsonforms(schema = r#""title": "Some Shape""#)]
+        shape: Shape,
+    }
+
+    #[test]
+    fn jsonforms_enum() {
+        let (s, uis) = Recurrence::jsonforms_schema();
+        let v: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(
+            v,
+            json!({"type": "string", "enum": ["never", "every_day", "weekly"]})
+        );
+        let v: serde_json::Value = serde_json::from_str(&uis).unwrap();
+        assert_eq!(v["elements"][0]["scope"], json!("#"));
+
+        let (s, _) = Contact::jsonforms_schema();
+        let v: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(
+            v["oneOf"][0],
+            json!({"title": "Unknown", "const": "Unknown"})
+        );
+        assert_eq!(
+            v["oneOf"][1]["properties"]["Email"],
+            json!({"type": "string"})
+        );
+        assert_eq!(v["oneOf"][2]["required"], json!(["Phone"]));
+        assert_eq!(
+            v["oneOf"][2]["properties"]["Phone"]["required"],
+            json!(["number"])
+        );
+        let contact: Contact = serde_json::from_str(r#"{"Email":"a@b.c"}"#).unwrap();
+        assert!(matches!(contact, Contact::Email(_)));
+
+        let (s, _) = Shape::jsonforms_schema();
+        let v: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(v["discriminator"], json!({"propertyName": "kind"}));
+        assert_eq!(
+            v["oneOf"][1]["properties"]["kind"],
+            json!({"const": "Circle"})
+        );
+        assert_eq!(v["oneOf"][1]["required"], json!(["kind", "radius"]));
+        assert_eq!(
+            v["oneOf"][2],
+            json!({
+                "title": "Square",
+                "type": "object",
+                "properties": {"kind": {"const": "Square"}, "side": {"type": "number", "minimum": 0}},
+                "required": ["kind", "side"],
+                "additionalProperties": false
+            })
+        );
+        let square: Shape = serde_json::from_str(r#"{"kind":"Square","side":-1}"#).unwrap();
+        assert_eq!(
+            square.validate(),
+            Err(vec![JsonFormsError::new("/side", "must be >= 0")])
+        );
+
+        let (s, uis) = TestJsonFormsEnum::jsonforms_schema();
+        let v: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(v["properties"]["recurrence"]["enum"][0], json!("never"));
+        assert!(v["properties"]["contact"]["oneOf"].is_array());
+        assert_eq!(v["properties"]["shape"]["title"], json!("Some Shape"));
+        assert_eq!(v["required"], json!(["recurrence", "shape"]));
+        let v: serde_json::Value = serde_json::from_str(&uis).unwrap();
+        assert_eq!(v["elements"][1]["scope"], json!("#/properties/contact"));
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct Address {
+        street: String,
+        #[jsonforms(HorizontalLayout)]
+        city: String,
+        #[jsonforms(EndLayout)]
+        zip: Option<String>,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct Contacts {
+        email: String,
+        address: Option<Address>,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsNested {
+        name: String,
+        #[jsonforms(uischema = r#""label": "Home Address""#)]
+        address: Address,
+        contacts: Contacts,
+    }
+
+    #[test]
+    fn jsonforms_nested() {
+        let (s, uis) = TestJsonFormsNested::jsonforms_schema();
+        let v: serde_json::Value = serde_json::from_str(&s).unwrap();
+        // Address is used twice, it is defined once in $defs
+        assert_eq!(
+            v["properties"]["address"],
+            json!({"$ref": "#/$defs/Address"})
+        );
+        assert_eq!(
+            v["$defs"]["Address"]["properties"]["street"],
+            json!({"type": "string"})
+        );
+        assert_eq!(v["$defs"]["Address"]["required"], json!(["street", "city"]));
+        assert_eq!(
+            v["properties"]["contacts"]["properties"]["address"],
+            json!({"$ref": "#/$defs/Address"})
+        );
+        assert_eq!(v["properties"]["contacts"]["type"], json!("object"));
+        assert_eq!(v["required"], json!(["name", "address", "contacts"]));
+
+        let v: serde_json::Value = serde_json::from_str(&uis).unwrap();
+        let address = &v["elements"][1];
+        assert_eq!(address["type"], json!("Group"));
+        assert_eq!(address["label"], json!("Home Address"));
+        let address_layout = &address["elements"][0];
+        assert_eq!(
+            address_layout["elements"][0]["scope"],
+            json!("#/properties/address/properties/street")
+        );
+        assert_eq!(
+            address_layout["elements"][1]["type"],
+            json!("HorizontalLayout")
+        );
+        assert_eq!(
+            address_layout["elements"][1]["elements"][1]["scope"],
+            json!("#/properties/address/properties/zip")
+        );
+        let contacts_layout = &v["elements"][2]["elements"][0];
+        assert_eq!(
+            contacts_layout["elements"][1]["elements"][0]["elements"][0]["scope"],
+            json!("#/properties/contacts/properties/address/properties/street")
+        );
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    struct Paging {
+        page_size: u32,
+        #[serde(default)]
+        page_index: u32,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    struct TestJsonFormsSerde {
+        first_name: String,
+        #[serde(rename = "surname")]
+        last_name: String,
+        #[serde(skip)]
+        cache: String,
+        #[serde(skip_deserializing)]
+        computed: Option<String>,
+        #[serde(default = "default_score")]
+        score: i32,
+        #[serde(flatten)]
+        paging: Paging,
+        r#type: String,
+    }
+
+    fn default_score() -> i32 {
+        10
+    }
+
+    #[derive(JsonForms, Deserialize, Default, Debug)]
+    #[serde(default)]
+    struct TestJsonFormsSerdeDefault {
+        name: String,
+        count: i32,
+    }
+
+    #[test]
+    fn jsonforms_serde() {
+        let (s, uis) = TestJsonFormsSerde::jsonforms_schema();
+        let v: serde_json::Value = serde_json::from_str(&s).unwrap();
+        let props = v["properties"].as_object().unwrap();
+        let mut names: Vec<_> = props.keys().map(|k| k.as_str()).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "firstName",
+                "pageIndex",
+                "pageSize",
+                "score",
+                "surname",
+                "type"
+            ]
+        );
+        assert_eq!(
+            v["required"],
+            json!(["firstName", "surname", "type", "pageSize"])
+        );
+        let v: serde_json::Value = serde_json::from_str(&uis).unwrap();
+        assert_eq!(v["elements"][0]["scope"], json!("#/properties/firstName"));
+        assert_eq!(v["elements"][1]["scope"], json!("#/properties/surname"));
+        assert_eq!(
+            v["elements"][3]["elements"][0]["elements"][0]["scope"],
+            json!("#/properties/pageSize")
+        );
+
+        let data: TestJsonFormsSerde =
+            serde_json::from_str(r#"{"firstName":"a","surname":"b","type":"c","pageSize":5}"#)
+                .unwrap();
+        assert_eq!(data.score, 10);
+        assert_eq!(data.paging.page_size, 5);
+
+        let (s, _) = TestJsonFormsSerdeDefault::jsonforms_schema();
+        let v: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert!(v.get("required").is_none());
+    }
+
+    /// contact email
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[serde(transparent)]
+    struct TestJsonFormsSerdeEmail {
+        #[jsonforms(format = "email")]
+        inner: String,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[serde(rename_all_fields = "camelCase")]
+    enum TestJsonFormsSerdeVariants {
+        Person {
+            first_name: String,
+        },
+        #[serde(rename_all = "UPPERCASE")]
+        Company {
+            legal_name: String,
+        },
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsSerdeContact {
+        email: TestJsonFormsSerdeEmail,
+        owner: TestJsonFormsSerdeVariants,
+    }
+
+    #[test]
+    fn jsonforms_serde_transparent_and_variants() {
+        let (v, uis) = TestJsonFormsSerdeContact::jsonforms_value();
+        assert_eq!(
+            v["properties"]["email"],
+            json!({"type": "string", "format": "email", "title": "contact email"})
+        );
+        assert_eq!(
+            uis["elements"][0],
+            json!({"type": "Control", "scope": "#/properties/email"})
+        );
+        let owner = &v["properties"]["owner"]["oneOf"];
+        assert_eq!(
+            owner[0]["properties"]["Person"]["required"],
+            json!(["firstName"])
+        );
+        assert_eq!(
+            owner[1]["properties"]["Company"]["required"],
+            json!(["LEGAL_NAME"])
+        );
+
+        let data: TestJsonFormsSerdeContact = serde_json::from_str(
+            r#"{"email":"a@example.com","owner":{"Person":{"firstName":"a"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(data.validate(), Ok(()));
+        let data: TestJsonFormsSerdeContact =
+            serde_json::from_str(r#"{"email":"a@","owner":{"Company":{"LEGAL_NAME":"b"}}}"#)
+                .unwrap();
+        assert_eq!(
+            data.validate(),
+            Err(vec![JsonFormsError::new(
+                "/email",
+                r#"must match format "email""#
+            )])
+        );
+    }
+
+    /// a marker serde writes as null
+    #[derive(JsonForms, Serialize, Deserialize, Debug)]
+    struct TestJsonFormsSerdeUnit;
+
+    #[derive(JsonForms, Serialize, Deserialize, Debug)]
+    struct TestJsonFormsSerdeMarked {
+        marker: TestJsonFormsSerdeUnit,
+    }
+
+    #[test]
+    fn jsonforms_serde_unit_struct() {
+        let (v, _) = TestJsonFormsSerdeUnit::jsonforms_value();
+        assert_eq!(
+            v,
+            json!({"title": "a marker serde writes as null", "type": "null"})
+        );
+        let data = TestJsonFormsSerdeMarked {
+            marker: TestJsonFormsSerdeUnit,
+        };
+        assert_eq!(
+            serde_json::to_value(&data).unwrap(),
+            json!({"marker": null})
+        );
+        let (v, _) = TestJsonFormsSerdeMarked::jsonforms_value();
+        assert_eq!(v["properties"]["marker"]["type"], json!("null"));
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsTyped {
+        #[jsonforms(title = "User \"Name\"", description = "Your login name")]
+        #[jsonforms(min_length = 4, max_length = 32, pattern = r"^\w+$")]
+        #[jsonforms(label = "Login")]
+        user: String,
+        #[jsonforms(minimum = -10, maximum = 2.5)]
+        temperature: f64,
+        #[jsonforms(format = "date", readonly)]
+        created: String,
+        #[jsonforms(multi, readonly = false)]
+        notes: Option<String>,
+    }
+
+    #[test]
+    fn jsonforms_typed_attrs() {
+        let (s, uis) = TestJsonFormsTyped::jsonforms_schema();
+        let v: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(
+            v["properties"]["user"],
+            json!({
+                "type": "string",
+                "title": "User \"Name\"",
+                "description": "Your login name",
+                "minLength": 4,
+                "maxLength": 32,
+                "pattern": "^\\w+$"
+            })
+        );
+        assert_eq!(
+            v["properties"]["temperature"],
+            json!({"type": "number", "minimum": -10, "maximum": 2.5})
+        );
+        assert_eq!(v["properties"]["created"]["format"], json!("date"));
+
+        let v: serde_json::Value = serde_json::from_str(&uis).unwrap();
+        assert_eq!(v["elements"][0]["label"], json!("Login"));
+        assert_eq!(v["elements"][2]["options"], json!({"readonly": true}));
+        assert_eq!(
+            v["elements"][3]["options"],
+            json!({"multi": true, "readonly": false})
+        );
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsNumbers {
+        byte: u8,
+        short: i16,
+        count: usize,
+        big: i128,
+        id: std::num::NonZeroU32,
+        delta: std::num::NonZero<i8>,
+        level: Option<u16>,
+        #[jsonforms(maximum = 100)]
+        percent: u8,
+        ratio: f32,
+    }
+
+    #[test]
+    fn jsonforms_numbers() {
+        let (schema, _) = TestJsonFormsNumbers::jsonforms_value();
+        let props = &schema["properties"];
+        assert_eq!(
+            props["byte"],
+            json!({"type": "integer", "minimum": 0, "maximum": 255})
+        );
+        assert_eq!(
+            props["short"],
+            json!({"type": "integer", "minimum": -32768, "maximum": 32767})
+        );
+        assert_eq!(props["count"]["maximum"], json!(usize::MAX));
+        assert_eq!(props["big"]["type"], json!("integer"));
+        assert_eq!(
+            props["id"],
+            json!({"type": "integer", "minimum": 1, "maximum": u32::MAX})
+        );
+        assert_eq!(
+            props["delta"],
+            json!({"type": "integer", "minimum": -128, "maximum": 127, "not": {"const": 0}})
+        );
+        assert_eq!(props["level"]["maximum"], json!(65535));
+        assert_eq!(
+            props["percent"],
+            json!({"type": "integer", "maximum": 100, "minimum": 0})
+        );
+        assert_eq!(props["ratio"], json!({"type": "number"}));
+        assert!(!schema["required"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("level")));
+    }
+
+    #[cfg(all(feature = "chrono", feature = "uuid", feature = "url"))]
+    #[derive(JsonForms)]
+    struct TestJsonFormsFormats {
+        due_date: chrono::NaiveDate,
+        created: chrono::DateTime<chrono::Utc>,
+        alarm: Option<chrono::NaiveTime>,
+        id: uuid::Uuid,
+        #[jsonforms(format = "uri-reference")]
+        link: url::Url,
+    }
+
+    #[cfg(all(feature = "chrono", feature = "uuid", feature = "url"))]
+    #[test]
+    fn jsonforms_formats() {
+        let (schema, _) = TestJsonFormsFormats::jsonforms_value();
+        let props = &schema["properties"];
+        assert_eq!(
+            props["due_date"],
+            json!({"type": "string", "format": "date"})
+        );
+        assert_eq!(props["created"]["format"], json!("date-time"));
+        assert_eq!(props["alarm"]["format"], json!("time"));
+        assert_eq!(props["id"]["format"], json!("uuid"));
+        assert_eq!(
+            props["link"],
+            json!({"type": "string", "format": "uri-reference"})
+        );
+        assert_eq!(
+            schema["required"],
+            json!(["due_date", "created", "id", "link"])
+        );
+    }
+
+    /// not the url crate type, despite the name
+    #[derive(JsonForms)]
+    struct Url {
+        path: String,
+    }
+
+    #[derive(JsonForms)]
+    struct TestJsonFormsOwnUrl {
+        link: Url,
+    }
+
+    #[test]
+    fn jsonforms_type_named_like_a_crate_type() {
+        let (schema, _) = TestJsonFormsOwnUrl::jsonforms_value();
+        assert_eq!(
+            schema["properties"]["link"]["properties"]["path"],
+            json!({"type": "string"})
+        );
+        assert_eq!(schema["properties"]["link"].get("format"), None);
+    }
+
+    #[test]
+    fn jsonforms_value() {
+        let (schema, uischema) = Address::jsonforms_value();
+        assert_eq!(schema["properties"]["city"], json!({"type": "string"}));
+        assert_eq!(uischema["type"], json!("VerticalLayout"));
+
+        let form = JsonFormsResponse::from(Address::jsonforms_value());
+        let v = serde_json::to_value(&form).unwrap();
+        assert_eq!(v["schema"], schema);
+        assert_eq!(v["uischema"], uischema);
+
+        let form = JsonFormsResponse::from(Address::jsonforms_schema());
+        assert_eq!(*form.schema, schema);
+        let form = JsonFormsResponse::from(Address::jsonforms_static());
+        assert!(matches!(form.schema, Cow::Borrowed(_)));
+        assert_eq!(*form.uischema, uischema);
+    }
+
+    struct HandWritten;
+
+    impl JsonFormsSerializable for HandWritten {
+        fn jsonforms_schema() -> (String, String) {
+            (
+                r#"{"type":"string"}"#.to_owned(),
+                r##"{"type":"Control","scope":"#"}"##.to_owned(),
+            )
+        }
+    }
+
+    #[test]
+    fn jsonforms_static() {
+        let cached = TestJsonForms001::jsonforms_static();
+        assert!(std::ptr::eq(cached, TestJsonForms001::jsonforms_static()));
+        assert_eq!(
+            (cached.schema.clone(), cached.uischema.clone()),
+            TestJsonForms001::jsonforms_schema()
+        );
+        assert_eq!(
+            cached.schema_value["properties"]["some_int"]["type"],
+            json!("integer")
+        );
+
+        let cached = HandWritten::jsonforms_static();
+        assert!(std::ptr::eq(cached, HandWritten::jsonforms_static()));
+        assert_eq!(cached.schema_value, json!({"type": "string"}));
+    }
+
+    /// Pet Form
+    ///
+    /// Register your pet,
+    /// all fields are checked.
+    ///
+    /// Thank you.
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsDoc {
+        /// Pet Name
+        /// as written in the passport
+        name: String,
+        /// Pet Age
+        #[jsonforms(title = "Age in years")]
+        age: u32,
+        /// Home Address
+        address: Address,
+        kind: PetKind,
+        /// Favorite Shape
+        #[jsonforms(description = "pick one")]
+        shape: Option<Shape>,
+        no_doc: bool,
+    }
+
+    /// Pet Kind
+    #[derive(JsonForms, Deserialize, Debug)]
+    enum PetKind {
+        Cat,
+        Dog,
+    }
+
+    #[test]
+    fn jsonforms_doc() {
+        let (v, ui) = TestJsonFormsDoc::jsonforms_value();
+        assert_eq!(v["title"], json!("Pet Form"));
+        assert_eq!(
+            v["description"],
+            json!("Register your pet, all fields are checked.\nThank you.")
+        );
+        let props = &v["properties"];
+        assert_eq!(props["name"]["title"], json!("Pet Name"));
+        assert_eq!(
+            props["name"]["description"],
+            json!("as written in the passport")
+        );
+        assert_eq!(props["age"]["title"], json!("Age in years"));
+        assert_eq!(props["address"]["title"], json!("Home Address"));
+        assert_eq!(props["address"]["type"], json!("object"));
+        assert_eq!(props["kind"]["title"], json!("Pet Kind"));
+        assert_eq!(props["shape"]["title"], json!("Favorite Shape"));
+        assert_eq!(props["shape"]["description"], json!("pick one"));
+        assert!(props["shape"]["oneOf"].is_array());
+        assert!(props["no_doc"].get("title").is_none());
+        assert_eq!(ui["elements"][2]["type"], json!("Group"));
+        assert_eq!(ui["elements"][2]["label"], json!("Home Address"));
+    }
+
+    #[derive(JsonForms)]
+    struct TestJsonFormsCollections {
+        homes: HashMap<String, Address>,
+        pages: Option<std::collections::BTreeMap<String, Paging>>,
+        pets: std::collections::HashSet<PetKind>,
+        kinds: std::collections::BTreeSet<PetKind>,
+        queue: std::collections::VecDeque<Address>,
+    }
+
+    #[test]
+    fn jsonforms_collections() {
+        let (v, _) = TestJsonFormsCollections::jsonforms_value();
+        let props = &v["properties"];
+        assert_eq!(props["homes"]["type"], json!("object"));
+        assert_eq!(
+            props["homes"]["additionalProperties"],
+            json!({"$ref": "#/$defs/Address"})
+        );
+        assert_eq!(v["$defs"]["Address"], Address::jsonforms_value().0);
+        assert!(props["homes"].get("items").is_none());
+        assert_eq!(
+            props["pages"]["additionalProperties"]["properties"]["pageSize"]["type"],
+            json!("integer")
+        );
+        assert_eq!(
+            props["pets"],
+            json!({"type": "array", "uniqueItems": true, "items": {"$ref": "#/$defs/PetKind"}})
+        );
+        assert_eq!(v["$defs"]["PetKind"], PetKind::jsonforms_value().0);
+        assert_eq!(props["kinds"]["uniqueItems"], json!(true));
+        assert_eq!(props["queue"]["type"], json!("array"));
+        assert!(props["queue"].get("uniqueItems").is_none());
+        assert_eq!(v["required"], json!(["homes", "pets", "kinds", "queue"]));
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsArrays {
+        tags: Vec<String>,
+        scores: Vec<i32>,
+        flags: Vec<bool>,
+        bytes: Option<Vec<u8>>,
+        matrix: Vec<Vec<f64>>,
+        addresses: Option<Vec<Address>>,
+        labels: HashMap<String, String>,
+    }
+
+    #[test]
+    fn jsonforms_arrays() {
+        let (v, ui) = TestJsonFormsArrays::jsonforms_value();
+        let props = &v["properties"];
+        assert_eq!(
+            props["tags"],
+            json!({"type": "array", "items": {"type": "string"}})
+        );
+        assert_eq!(props["scores"]["items"]["type"], json!("integer"));
+        assert_eq!(
+            props["flags"],
+            json!({"type": "array", "items": {"type": "boolean"}})
+        );
+        assert_eq!(
+            props["bytes"],
+            json!({"type": "array", "items": {"type": "integer", "minimum": 0, "maximum": 255}})
+        );
+        assert_eq!(
+            props["matrix"],
+            json!({"type": "array", "items": {"type": "array", "items": {"type": "number"}}})
+        );
+        assert_eq!(props["addresses"]["items"], Address::jsonforms_value().0);
+        assert_eq!(
+            props["labels"]["additionalProperties"],
+            json!({"type": "string"})
+        );
+        assert_eq!(
+            v["required"],
+            json!(["tags", "scores", "flags", "matrix", "labels"])
+        );
+        assert_eq!(ui["elements"][3]["scope"], json!("#/properties/bytes"));
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Email(String);
+
+    impl JsonFormsField for Email {
+        fn jsonforms_field_schema(_defs: &mut JsonFormsDefs) -> Cow<'static, str> {
+            Cow::Borrowed(r#"{"type":"string","format":"email"}"#)
+        }
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsFields {
+        #[jsonforms(title = "Work Email")]
+        email: Email,
+        backup: Option<Email>,
+        others: Vec<Email>,
+        by_name: HashMap<String, Email>,
+        rgb: [u8; 3],
+        id: std::num::NonZeroU64,
+        initial: char,
+    }
+
+    #[test]
+    fn jsonforms_fields() {
+        let (v, ui) = TestJsonFormsFields::jsonforms_value();
+        let props = &v["properties"];
+        assert_eq!(
+            props["email"],
+            json!({"type": "string", "format": "email", "title": "Work Email"})
+        );
+        assert_eq!(props["backup"]["format"], json!("email"));
+        assert_eq!(props["others"]["items"]["format"], json!("email"));
+        assert_eq!(
+            props["by_name"]["additionalProperties"]["format"],
+            json!("email")
+        );
+        assert_eq!(
+            props["rgb"],
+            json!({"type": "array", "minItems": 3, "maxItems": 3,
+                "items": {"type": "integer", "minimum": 0, "maximum": 255}})
+        );
+        assert_eq!(props["initial"]["maxLength"], json!(1));
+        assert_eq!(ui["elements"][0]["type"], json!("Control"));
+        assert_eq!(ui["elements"][0]["scope"], json!("#/properties/email"));
+        assert_eq!(ui["elements"][0]["label"], json!("Work Email"));
+        assert_eq!(ui["elements"][1]["scope"], json!("#/properties/backup"));
+
+        // std impls match the schemas the derive inlines
+        let (arrays, _) = TestJsonFormsArrays::jsonforms_value();
+        let (numbers, _) = TestJsonFormsNumbers::jsonforms_value();
+        let field = |schema: fn(&mut JsonFormsDefs) -> Cow<'static, str>| {
+            let mut defs = JsonFormsDefs::new();
+            let schema = schema(&mut defs).into_owned();
+            serde_json::from_str::<serde_json::Value>(&defs.schema(schema)).unwrap()
+        };
+        assert_eq!(
+            field(<Vec<Vec<f64>>>::jsonforms_field_schema),
+            arrays["properties"]["matrix"]
+        );
+        assert_eq!(
+            field(<Option<Vec<u8>>>::jsonforms_field_schema),
+            arrays["properties"]["bytes"]
+        );
+        assert_eq!(
+            field(<HashMap<String, String>>::jsonforms_field_schema),
+            arrays["properties"]["labels"]
+        );
+        assert_eq!(
+            field(<i16>::jsonforms_field_schema),
+            numbers["properties"]["short"]
+        );
+        assert_eq!(
+            field(<std::num::NonZero<i8>>::jsonforms_field_schema),
+            numbers["properties"]["delta"]
+        );
+        assert_eq!(
+            field(<std::num::NonZeroU32>::jsonforms_field_schema),
+            numbers["properties"]["id"]
+        );
+        assert_eq!(
+            field(<Option<Address>>::jsonforms_field_schema),
+            Address::jsonforms_value().0
+        );
+    }
+
+    /// Page
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct Page<T> {
+        items: Vec<T>,
+        total: u32,
+    }
+
+    #[derive(JsonForms, Debug)]
+    struct Envelope<'a, M, const N: usize>
+    where
+        M: Clone,
+    {
+        message: Option<M>,
+        codes: [u8; N],
+        #[jsonforms(Skip)]
+        sender: std::marker::PhantomData<&'a str>,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    enum Either<L, R> {
+        Left(L),
+        Right(R),
+    }
+
+    #[test]
+    fn jsonforms_generics() {
+        let (pages, ui) = Page::<Address>::jsonforms_value();
+        assert_eq!(pages["title"], json!("Page"));
+        assert_eq!(
+            pages["properties"]["items"]["items"],
+            Address::jsonforms_value().0
+        );
+        assert_eq!(ui["elements"][0]["scope"], json!("#/properties/items"));
+        // every instantiation has its own cached schema
+        let (names, _) = Page::<String>::jsonforms_value();
+        assert_eq!(
+            names["properties"]["items"]["items"],
+            json!({"type": "string"})
+        );
+        let (nested, _) = Page::<Page<bool>>::jsonforms_value();
+        assert_eq!(
+            nested["properties"]["items"]["items"]["properties"]["items"]["items"],
+            json!({"type": "boolean"})
+        );
+
+        let (v, _) = Envelope::<String, 2>::jsonforms_value();
+        assert_eq!(v["properties"]["message"], json!({"type": "string"}));
+        assert_eq!(v["properties"]["codes"]["maxItems"], json!(2));
+        assert_eq!(
+            Envelope::<String, 4>::jsonforms_value().0["properties"]["codes"]["maxItems"],
+            json!(4)
+        );
+
+        let (v, _) = Either::<u8, Address>::jsonforms_value();
+        assert_eq!(v["oneOf"][0]["properties"]["Left"]["maximum"], json!(255));
+        assert_eq!(
+            v["oneOf"][1]["properties"]["Right"],
+            Address::jsonforms_value().0
+        );
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TreeNode {
+        label: String,
+        children: Vec<TreeNode>,
+        parent: Option<Box<TreeNode>>,
+    }
+
+    mod other {
+        use super::*;
+
+        #[derive(JsonForms, Deserialize, Debug)]
+        pub struct Address {
+            pub country: String,
+        }
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsDefs {
+        root: TreeNode,
+        home: Address,
+        work: Address,
+        abroad: other::Address,
+        previous: Option<other::Address>,
+        pages: Vec<Page<Address>>,
+    }
+
+    #[test]
+    fn jsonforms_defs() {
+        let (tree, ui) = TreeNode::jsonforms_value();
+        assert_eq!(
+            tree["properties"]["children"]["items"],
+            json!({"$ref": "#/$defs/TreeNode"})
+        );
+        assert_eq!(
+            tree["properties"]["parent"],
+            json!({"$ref": "#/$defs/TreeNode"})
+        );
+        assert_eq!(tree["required"], json!(["label", "children"]));
+        assert_eq!(tree["$defs"]["TreeNode"]["properties"], tree["properties"]);
+        // the recursive field is a Group once, then a Control
+        let parent = &ui["elements"][2];
+        assert_eq!(parent["type"], json!("Group"));
+        assert_eq!(
+            parent["elements"][0]["elements"][2],
+            json!({"type": "Control", "scope": "#/properties/parent/properties/parent"})
+        );
+
+        let (v, _) = TestJsonFormsDefs::jsonforms_value();
+        let props = &v["properties"];
+        assert_eq!(props["root"]["$ref"], json!("#/$defs/TreeNode"));
+        assert_eq!(props["home"]["$ref"], json!("#/$defs/Address"));
+        assert_eq!(props["work"]["$ref"], json!("#/$defs/Address"));
+        // same name in another module
+        assert_eq!(props["abroad"]["$ref"], json!("#/$defs/Address2"));
+        assert_eq!(
+            props["pages"]["items"]["properties"]["items"]["items"],
+            json!({"$ref": "#/$defs/Address"})
+        );
+        let defs = v["$defs"].as_object().unwrap();
+        assert_eq!(
+            defs.keys().collect::<Vec<_>>(),
+            vec!["Address", "Address2", "TreeNode"]
+        );
+        assert_eq!(
+            defs["Address"]["properties"]["street"],
+            json!({"type": "string"})
+        );
+        assert_eq!(defs["Address2"]["required"], json!(["country"]));
+        // Page<Address> is used once, it stays inline
+        assert_eq!(props["pages"]["items"]["type"], json!("object"));
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsLayouts {
+        #[jsonforms(Categorization, Category = "Person")]
+        #[jsonforms(Group = "Name", HorizontalLayout)]
+        first: String,
+        #[jsonforms(EndLayout, EndLayout)]
+        last: String,
+        #[jsonforms(EndLayout)]
+        age: u8,
+        #[jsonforms(Category = "Home", EndLayout, EndLayout)]
+        address: Address,
+        #[jsonforms(Group, EndLayout)]
+        notes: String,
+    }
+
+    #[test]
+    fn jsonforms_layouts() {
+        let (_, ui) = TestJsonFormsLayouts::jsonforms_value();
+        let control =
+            |name: &str| json!({"type": "Control", "scope": format!("#/properties/{}", name)});
+        assert_eq!(ui["type"], json!("VerticalLayout"));
+        let tabs = &ui["elements"][0];
+        assert_eq!(tabs["type"], json!("Categorization"));
+        let person = &tabs["elements"][0];
+        assert_eq!(person["type"], json!("Category"));
+        assert_eq!(person["label"], json!("Person"));
+        assert_eq!(
+            person["elements"],
+            json!([
+                {"type": "Group", "label": "Name", "elements": [
+                    {"type": "HorizontalLayout", "elements": [control("first"), control("last")]}
+                ]},
+                control("age")
+            ])
+        );
+        let home = &tabs["elements"][1];
+        assert_eq!(home["label"], json!("Home"));
+        assert_eq!(home["elements"][0]["type"], json!("Group"));
+        assert_eq!(
+            ui["elements"][1],
+            json!({"type": "Group", "elements": [control("notes")]})
+        );
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    struct TestJsonFormsRules {
+        recurrence: Recurrence,
+        #[jsonforms(hide_if(field = "recurrence", equals = "never"))]
+        recurrence_interval: Option<u32>,
+        age: u8,
+        #[jsonforms(
+            label = "Driver",
+            disable_if(field = "age", schema = r#"{"maximum": 17}"#)
+        )]
+        licensed: bool,
+        #[jsonforms(show_if(any(
+            when(field = "licensed", equals = true),
+            all(
+                when(field = "age", equals = 17),
+                when(field = "recurrenceInterval", equals = 1)
+            )
+        )))]
+        address: Option<Address>,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsRulesNested {
+        rules: TestJsonFormsRules,
+    }
+
+    #[test]
+    fn jsonforms_rules() {
+        let (_, ui) = TestJsonFormsRules::jsonforms_value();
+        assert_eq!(
+            ui["elements"][1]["rule"],
+            json!({"effect": "HIDE", "condition": {
+                "scope": "#/properties/recurrence",
+                "schema": {"const": "never"}
+            }})
+        );
+        assert_eq!(
+            ui["elements"][3],
+            json!({"type": "Control", "scope": "#/properties/licensed", "label": "Driver",
+                "rule": {"effect": "DISABLE", "condition": {
+                    "scope": "#/properties/age",
+                    "schema": {"maximum": 17}
+                }}
+            })
+        );
+        let address = &ui["elements"][4];
+        assert_eq!(address["type"], json!("Group"));
+        assert_eq!(
+            address["rule"],
+            json!({"effect": "SHOW", "condition": {"type": "OR", "conditions": [
+                {"scope": "#/properties/licensed", "schema": {"const": true}},
+                {"type": "AND", "conditions": [
+                    {"scope": "#/properties/age", "schema": {"const": 17}},
+                    {"scope": "#/properties/recurrenceInterval", "schema": {"const": 1}}
+                ]}
+            ]}})
+        );
+
+        // scopes follow the nesting of the form
+        let (_, ui) = TestJsonFormsRulesNested::jsonforms_value();
+        assert_eq!(
+            ui["elements"][0]["elements"][0]["elements"][1]["rule"]["condition"]["scope"],
+            json!("#/properties/rules/properties/recurrence")
+        );
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[jsonforms(conditional(
+        if(field = "country", equals = "US"),
+        then = r#"{"required": ["state"]}"#,
+        else = r#"{"required": ["postcode"]}"#
+    ))]
+    struct TestJsonFormsCrossField {
+        password: String,
+        #[jsonforms(must_equal = "password")]
+        confirm_password: String,
+        recurrence: Recurrence,
+        #[jsonforms(required_if(any(
+            when(field = "recurrence", equals = "every_day"),
+            when(field = "recurrence", equals = "weekly")
+        )))]
+        interval: Option<u32>,
+        #[jsonforms(dependent_required = "zip", dependent_required = "city")]
+        street: Option<String>,
+        zip: Option<String>,
+        city: String,
+        country: String,
+        state: Option<String>,
+        postcode: Option<String>,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    enum TestJsonFormsCrossFieldEnum {
+        Open,
+        Pin {
+            pin: String,
+            #[jsonforms(must_equal = "pin")]
+            repeat: String,
+        },
+    }
+
+    #[test]
+    fn jsonforms_cross_field() {
+        let (v, _) = TestJsonFormsCrossField::jsonforms_value();
+        assert_eq!(
+            v["properties"]["confirm_password"],
+            json!({"type": "string", "const": {"$data": "1/password"}})
+        );
+        assert_eq!(v["dependentRequired"], json!({"street": ["zip", "city"]}));
+        assert_eq!(
+            v["allOf"],
+            json!([
+                {
+                    "if": {"properties": {"country": {"const": "US"}}, "required": ["country"]},
+                    "then": {"required": ["state"]},
+                    "else": {"required": ["postcode"]}
+                },
+                {
+                    "if": {"anyOf": [
+                        {"properties": {"recurrence": {"const": "every_day"}}, "required": ["recurrence"]},
+                        {"properties": {"recurrence": {"const": "weekly"}}, "required": ["recurrence"]}
+                    ]},
+                    "then": {"required": ["interval"]}
+                }
+            ])
+        );
+
+        let mut form = TestJsonFormsCrossField {
+            password: "secret".to_owned(),
+            confirm_password: "secret".to_owned(),
+            recurrence: Recurrence::Never,
+            interval: None,
+            street: None,
+            zip: None,
+            city: "Paris".to_owned(),
+            country: "FR".to_owned(),
+            state: None,
+            postcode: Some("75001".to_owned()),
+        };
+        assert_eq!(form.validate(), Ok(()));
+        form.confirm_password = "secreT".to_owned();
+        form.recurrence = Recurrence::Weekly;
+        form.street = Some("Main St".to_owned());
+        form.country = "US".to_owned();
+        let errors = form.validate().unwrap_err();
+        assert_eq!(
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "/state is required",
+                "/confirm_password must be equal to password",
+                "/interval is required",
+                "/zip is required when street is set",
+            ]
+        );
+
+        let pin = TestJsonFormsCrossFieldEnum::Pin {
+            pin: "1234".to_owned(),
+            repeat: "4321".to_owned(),
+        };
+        assert_eq!(
+            pin.validate(),
+            Err(vec![JsonFormsError::new(
+                "/Pin/repeat",
+                "must be equal to pin"
+            )])
+        );
+        assert_eq!(TestJsonFormsCrossFieldEnum::Open.validate(), Ok(()));
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[jsonforms(conditional(
+        if(field = "kind", equals = "phone"),
+        then = r#"{"properties": {"contact": {"pattern": "^[0-9 +]+$"}}}"#,
+        else = r#"{"properties": {"contact": {"format": "email"}}, "required": ["name"]}"#
+    ))]
+    struct TestJsonFormsCheckBranches {
+        kind: String,
+        contact: String,
+        name: Option<String>,
+    }
+
+    #[test]
+    fn jsonforms_check_branches() {
+        let form = |kind: &str, contact: &str, name: Option<&str>| TestJsonFormsCheckBranches {
+            kind: kind.to_owned(),
+            contact: contact.to_owned(),
+            name: name.map(str::to_owned),
+        };
+        assert_eq!(form("phone", "+33 1 23", None).validate(), Ok(()));
+        assert_eq!(
+            form("phone", "a@example.com", None).validate(),
+            Err(vec![JsonFormsError::new(
+                "/contact",
+                r#"must match pattern "^[0-9 +]+$""#
+            )])
+        );
+        assert_eq!(
+            form("mail", "+33 1 23", None).validate(),
+            Err(vec![
+                JsonFormsError::new("/contact", r#"must match format "email""#),
+                JsonFormsError::new("/name", "is required"),
+            ])
+        );
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsCheckItem {
+        #[jsonforms(min_length = 3, pattern = "^[a-z]+$")]
+        name: String,
+        #[jsonforms(minimum = 1, maximum = 10)]
+        qty: u32,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    enum TestJsonFormsCheckChoice {
+        Nothing,
+        Item(TestJsonFormsCheckItem),
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsValidate {
+        #[jsonforms(max_length = 5)]
+        code: Option<String>,
+        #[jsonforms(schema = r#""enum": ["red", "green"]"#)]
+        color: String,
+        #[jsonforms(schema = r#""minItems": 1, "uniqueItems": true"#)]
+        tags: Vec<String>,
+        items: Vec<TestJsonFormsCheckItem>,
+        by_key: std::collections::BTreeMap<String, TestJsonFormsCheckItem>,
+        choice: TestJsonFormsCheckChoice,
+        boxed: Option<Box<TestJsonFormsCheckItem>>,
+    }
+
+    #[test]
+    fn jsonforms_validate() {
+        let item = |name: &str, qty| TestJsonFormsCheckItem {
+            name: name.to_owned(),
+            qty,
+        };
+        let mut form = TestJsonFormsValidate {
+            code: None,
+            color: "red".to_owned(),
+            tags: vec!["a".to_owned()],
+            items: vec![item("abc", 1)],
+            by_key: std::collections::BTreeMap::from([("k/1".to_owned(), item("xyz", 10))]),
+            choice: TestJsonFormsCheckChoice::Nothing,
+            boxed: None,
+        };
+        assert_eq!(form.validate(), Ok(()));
+
+        form.code = Some("123456".to_owned());
+        form.color = "blue".to_owned();
+        form.tags = vec!["a".to_owned(), "a".to_owned()];
+        form.items.push(item("AB", 0));
+        form.by_key.insert("k2".to_owned(), item("abc", 11));
+        form.choice = TestJsonFormsCheckChoice::Item(item("abcd", 20));
+        form.boxed = Some(Box::new(item("ab", 5)));
+        let errors = form.validate().unwrap_err();
+        assert_eq!(
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "/code must NOT have more than 5 characters",
+                "/color must be equal to one of the allowed values",
+                "/tags must NOT have duplicate items",
+                "/items/1/name must NOT have fewer than 3 characters",
+                "/items/1/name must match pattern \"^[a-z]+$\"",
+                "/items/1/qty must be >= 1",
+                "/by_key/k2/qty must be <= 10",
+                "/choice/Item/qty must be <= 10",
+                "/boxed/name must NOT have fewer than 3 characters",
+            ]
+        );
+
+        // std collections check their items too
+        let mut errors = Vec::new();
+        vec![Some(item("a", 1))].jsonforms_field_check("/list", &mut errors);
+        assert_eq!(
+            errors,
+            vec![JsonFormsError::new(
+                "/list/0/name",
+                "must NOT have fewer than 3 characters"
+            )]
+        );
+    }
+
+    #[derive(JsonForms, Serialize, Deserialize, Debug)]
+    struct TestJsonFormsCheckPaging {
+        #[jsonforms(minimum = 1)]
+        page_size: u32,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsCheckFlatten {
+        name: String,
+        #[serde(flatten)]
+        paging: Option<TestJsonFormsCheckPaging>,
+    }
+
+    #[test]
+    fn jsonforms_validate_flatten_option() {
+        let form = |paging| TestJsonFormsCheckFlatten {
+            name: "a".to_owned(),
+            paging,
+        };
+        assert_eq!(form(None).validate(), Ok(()));
+        let (s, _) = TestJsonFormsCheckFlatten::jsonforms_schema();
+        let schema: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(schema["required"], json!(["name"]));
+        assert_eq!(schema["properties"]["page_size"]["minimum"], json!(1));
+        assert_eq!(
+            form(Some(TestJsonFormsCheckPaging { page_size: 0 })).validate(),
+            Err(vec![JsonFormsError::new("/page_size", "must be >= 1")])
+        );
+    }
+
+    /// a row of a request, only ever deserialized
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsCheckRow {
+        #[jsonforms(min_length = 1)]
+        name: String,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    enum TestJsonFormsCheckKind {
+        #[serde(rename = "daily")]
+        Daily,
+        Weekly,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsCheckPage<T> {
+        #[jsonforms(min_items = 1, max_items = 2)]
+        items: Vec<T>,
+        #[jsonforms(schema = r#""const": "daily""#)]
+        kind: TestJsonFormsCheckKind,
+    }
+
+    #[test]
+    fn jsonforms_check_deserialize_only() {
+        let row = |name: &str| TestJsonFormsCheckRow {
+            name: name.to_owned(),
+        };
+        let page = |items, kind| TestJsonFormsCheckPage { items, kind };
+        assert_eq!(
+            page(vec![row("a")], TestJsonFormsCheckKind::Daily).validate(),
+            Ok(())
+        );
+        assert_eq!(
+            page(vec![], TestJsonFormsCheckKind::Weekly).validate(),
+            Err(vec![
+                JsonFormsError::new("/items", "must NOT have fewer than 1 items"),
+                JsonFormsError::new("/kind", "must be equal to constant"),
+            ])
+        );
+        assert_eq!(
+            page(
+                vec![row(""), row("b"), row("c")],
+                TestJsonFormsCheckKind::Daily
+            )
+            .validate(),
+            Err(vec![
+                JsonFormsError::new("/items", "must NOT have more than 2 items"),
+                JsonFormsError::new("/items/0/name", "must NOT have fewer than 1 characters"),
+            ])
+        );
+    }
+
+    #[test]
+    fn jsonforms_check_generic_maps_and_boxes() {
+        let row = |name: &str| TestJsonFormsCheckRow {
+            name: name.to_owned(),
+        };
+        let rows = std::collections::BTreeMap::from([("a/b".to_owned(), row(""))]);
+        let page = TestJsonFormsCheckPage {
+            items: vec![rows],
+            kind: TestJsonFormsCheckKind::Daily,
+        };
+        assert_eq!(
+            page.validate(),
+            Err(vec![JsonFormsError::new(
+                "/items/0/a~1b/name",
+                "must NOT have fewer than 1 characters"
+            )])
+        );
+        let page = TestJsonFormsCheckPage {
+            items: vec![Box::new(row("a")), Box::new(row(""))],
+            kind: TestJsonFormsCheckKind::Daily,
+        };
+        assert_eq!(
+            page.validate(),
+            Err(vec![JsonFormsError::new(
+                "/items/1/name",
+                "must NOT have fewer than 1 characters"
+            )])
+        );
+        let (s, _) = TestJsonFormsCheckPage::<Box<TestJsonFormsCheckRow>>::jsonforms_schema();
+        let schema: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(
+            schema["properties"]["items"]["items"]["properties"]["name"]["minLength"],
+            json!(1)
+        );
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsCheckFormats {
+        #[jsonforms(format = "email")]
+        email: String,
+        #[jsonforms(format = "date")]
+        day: String,
+        #[jsonforms(schema = r#""format": "date-time""#)]
+        at: String,
+    }
+
+    #[test]
+    fn jsonforms_check_formats() {
+        let form = |email: &str, day: &str, at: &str| TestJsonFormsCheckFormats {
+            email: email.to_owned(),
+            day: day.to_owned(),
+            at: at.to_owned(),
+        };
+        assert_eq!(
+            form("a.b@example.com", "2024-02-29", "2024-01-31T08:00:00Z").validate(),
+            Ok(())
+        );
+        assert_eq!(
+            form("a.b@", "2023-02-29", "2024-01-31T08:00:00").validate(),
+            Err(vec![
+                JsonFormsError::new("/email", r#"must match format "email""#),
+                JsonFormsError::new("/day", r#"must match format "date""#),
+                JsonFormsError::new("/at", r#"must match format "date-time""#),
+            ])
+        );
+    }
+
+    #[test]
+    fn jsonforms_check_unsupported_pattern() {
+        let mut errors = Vec::new();
+        jsonforms_check_value(
+            &"lower".to_owned(),
+            r#"{"pattern":"^(?=.*[A-Z]).+$"}"#,
+            "/p",
+            &mut errors,
+        );
+        assert_eq!(
+            errors,
+            vec![JsonFormsError::new(
+                "/p",
+                r#"must match pattern "^(?=.*[A-Z]).+$""#
+            )]
+        );
+    }
+
+    fn not_reserved(name: &str, _form: &TestJsonFormsHooks) -> Result<(), &'static str> {
+        if name == "admin" {
+            Err("is reserved")
+        } else {
+            Ok(())
+        }
+    }
+
+    fn in_order(form: &TestJsonFormsHooks) -> Result<(), Vec<JsonFormsError>> {
+        if form.start > form.end {
+            Err(vec![JsonFormsError::new("/end", "must be after start")])
+        } else {
+            Ok(())
+        }
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[jsonforms(validate_with = "in_order")]
+    struct TestJsonFormsHooks {
+        #[jsonforms(min_length = 3, validate_with = "not_reserved")]
+        name: String,
+        start: u32,
+        end: u32,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsHooksList {
+        hooks: Vec<TestJsonFormsHooks>,
+    }
+
+    #[test]
+    fn jsonforms_validate_with() {
+        let hooks = |name: &str, start, end| TestJsonFormsHooks {
+            name: name.to_owned(),
+            start,
+            end,
+        };
+        assert_eq!(hooks("guest", 1, 2).validate(), Ok(()));
+        let list = TestJsonFormsHooksList {
+            hooks: vec![hooks("guest", 1, 2), hooks("admin", 3, 2)],
+        };
+        assert_eq!(
+            list.validate(),
+            Err(vec![
+                JsonFormsError::new("/hooks/1/name", "is reserved"),
+                JsonFormsError::new("/hooks/1/end", "must be after start"),
+            ])
+        );
+    }
+
+    /// User id
+    #[derive(JsonForms, Serialize, Deserialize, Debug)]
+    struct TestJsonFormsUserId(#[jsonforms(minimum = 1)] i32);
+
+    #[derive(JsonForms, Serialize, Deserialize, Debug)]
+    struct TestJsonFormsPoint(f64, #[jsonforms(maximum = 10)] f64);
+
+    #[derive(JsonForms, Serialize, Deserialize, Debug)]
+    struct TestJsonFormsAddressId(TestJsonFormsAddress);
+
+    #[derive(JsonForms, Serialize, Deserialize, Debug)]
+    struct TestJsonFormsAddress {
+        street: String,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsTuples {
+        id: TestJsonFormsUserId,
+        point: TestJsonFormsPoint,
+        address: TestJsonFormsAddressId,
+    }
+
+    #[test]
+    fn jsonforms_tuple_structs() {
+        let (s, uis) = TestJsonFormsTuples::jsonforms_schema();
+        let schema: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(
+            schema["properties"]["id"],
+            json!({"type":"integer","minimum":1,"maximum":i32::MAX,"title":"User id"})
+        );
+        assert_eq!(
+            schema["properties"]["point"],
+            json!({
+                "type":"array",
+                "prefixItems":[{"type":"number"},{"type":"number","maximum":10}],
+                "items":[{"type":"number"},{"type":"number","maximum":10}],
+                "minItems":2,
+                "maxItems":2
+            })
+        );
+        assert_eq!(
+            schema["properties"]["address"]["properties"]["street"],
+            json!({"type":"string"})
+        );
+        let uischema: serde_json::Value = serde_json::from_str(&uis).unwrap();
+        assert_eq!(
+            uischema["elements"][1],
+            json!({"type":"Control","scope":"#/properties/point"})
+        );
+        assert_eq!(uischema["elements"][2]["type"], json!("Group"));
+        let form = TestJsonFormsTuples {
+            id: TestJsonFormsUserId(0),
+            point: TestJsonFormsPoint(1.0, 11.0),
+            address: TestJsonFormsAddressId(TestJsonFormsAddress {
+                street: String::new(),
+            }),
+        };
+        assert_eq!(
+            form.validate(),
+            Err(vec![
+                JsonFormsError::new("/id", "must be >= 1"),
+                JsonFormsError::new("/point/1", "must be <= 10"),
+            ])
+        );
+    }
+
+    /// Doc title
+    ///
+    /// Doc description
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[jsonforms(title = "Sign Up", id = "https://example.com/sign-up.json")]
+    #[jsonforms(deny_unknown_fields)]
+    struct TestJsonFormsMeta {
+        name: String,
+        inner: TestJsonFormsMetaInner,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[serde(deny_unknown_fields)]
+    struct TestJsonFormsMetaInner {
+        value: i32,
+    }
+
+    #[test]
+    fn jsonforms_form_meta() {
+        let (s, _) = TestJsonFormsMeta::jsonforms_schema();
+        let schema: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(schema["title"], json!("Sign Up"));
+        assert_eq!(schema["description"], json!("Doc description"));
+        assert_eq!(schema["$id"], json!("https://example.com/sign-up.json"));
+        assert_eq!(schema["additionalProperties"], json!(false));
+        let inner = &schema["properties"]["inner"];
+        assert_eq!(inner["additionalProperties"], json!(false));
+        assert!(inner.get("$id").is_none());
+        let form = JsonFormsResponse::from(TestJsonFormsMeta::jsonforms_static());
+        assert_eq!(form.title.as_deref(), Some("Sign Up"));
+        let form = JsonFormsResponse::from(TestJsonFormsMetaInner::jsonforms_static());
+        assert_eq!(form.title, None);
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[jsonforms(default)]
+    struct TestJsonFormsDefaults {
+        host: String,
+        #[jsonforms(default = 8080)]
+        port: u16,
+        #[serde(default)]
+        retries: u8,
+        proxy: Option<String>,
+        tags: Vec<String>,
+    }
+
+    impl Default for TestJsonFormsDefaults {
+        fn default() -> Self {
+            Self {
+                host: "localhost".to_owned(),
+                port: 80,
+                retries: 3,
+                proxy: None,
+                tags: vec!["web".to_owned()],
+            }
+        }
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsFieldDefault {
+        #[jsonforms(default = "guest")]
+        name: String,
+    }
+
+    #[test]
+    fn jsonforms_defaults() {
+        let (s, _) = TestJsonFormsDefaults::jsonforms_schema();
+        let schema: serde_json::Value = serde_json::from_str(&s).unwrap();
+        let props = &schema["properties"];
+        assert_eq!(props["host"]["default"], json!("localhost"));
+        assert_eq!(props["port"]["default"], json!(8080));
+        assert_eq!(props["retries"]["default"], json!(3));
+        assert!(props["proxy"].get("default").is_none());
+        assert_eq!(props["tags"]["default"], json!(["web"]));
+        assert_eq!(schema["required"], json!(["host", "port", "tags"]));
+        let (s, _) = TestJsonFormsFieldDefault::jsonforms_schema();
+        let schema: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(schema["properties"]["name"]["default"], json!("guest"));
+    }
+
+    #[derive(JsonForms, Serialize, Deserialize, Debug)]
+    struct TestJsonFormsRow {
+        name: String,
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsArrayDetail {
+        #[jsonforms(show_sort_buttons = true, element_label_prop = "name")]
+        #[jsonforms(min_items = 1, max_items = 3)]
+        rows: Vec<TestJsonFormsRow>,
+        #[jsonforms(array = "table")]
+        table: Vec<TestJsonFormsRow>,
+        #[jsonforms(array = "list")]
+        list: Vec<TestJsonFormsRow>,
+        tags: Vec<String>,
+        #[jsonforms(show_sort_buttons = true)]
+        recurrences: Vec<Recurrence>,
+    }
+
+    #[test]
+    fn jsonforms_array_detail() {
+        let (s, uis) = TestJsonFormsArrayDetail::jsonforms_schema();
+        let schema: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(schema["properties"]["rows"]["minItems"], json!(1));
+        assert_eq!(schema["properties"]["rows"]["maxItems"], json!(3));
+        let uischema: serde_json::Value = serde_json::from_str(&uis).unwrap();
+        let detail = json!({
+            "type":"Group",
+            "elements":[{
+                "type":"VerticalLayout",
+                "elements":[{"type":"Control","scope":"#/properties/name"}]
+            }]
+        });
+        assert_eq!(
+            uischema["elements"][0],
+            json!({
+                "type":"Control",
+                "scope":"#/properties/rows",
+                "options":{
+                    "showSortButtons":true,
+                    "elementLabelProp":"name",
+                    "detail":detail
+                }
+            })
+        );
+        assert_eq!(
+            uischema["elements"][1],
+            json!({"type":"Control","scope":"#/properties/table"})
+        );
+        assert_eq!(
+            uischema["elements"][2],
+            json!({
+                "type":"ListWithDetail",
+                "scope":"#/properties/list",
+                "options":{"detail":detail}
+            })
+        );
+        assert_eq!(
+            uischema["elements"][3],
+            json!({"type":"Control","scope":"#/properties/tags"})
+        );
+        assert_eq!(
+            uischema["elements"][4],
+            json!({
+                "type":"Control",
+                "scope":"#/properties/recurrences",
+                "options":{"showSortButtons":true}
+            })
+        );
+        let form = TestJsonFormsArrayDetail {
+            rows: Vec::new(),
+            table: Vec::new(),
+            list: Vec::new(),
+            tags: Vec::new(),
+            recurrences: Vec::new(),
+        };
+        assert_eq!(
+            form.validate(),
+            Err(vec![JsonFormsError::new(
+                "/rows",
+                "must NOT have fewer than 1 items"
+            )])
+        );
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    #[jsonforms(layout(name = "address", Group = "Address"))]
+    #[jsonforms(layout(name = "street", HorizontalLayout, layout = "address"))]
+    struct TestJsonFormsOrder {
+        #[jsonforms(order = 1)]
+        id: u32,
+        #[jsonforms(order = -1)]
+        name: String,
+        #[jsonforms(layout = "street", order = 1)]
+        number: u32,
+        #[jsonforms(layout = "street")]
+        street: String,
+        #[jsonforms(layout = "address")]
+        city: String,
+    }
+
+    #[test]
+    fn jsonforms_order() {
+        let (_, uis) = TestJsonFormsOrder::jsonforms_schema();
+        let uischema: serde_json::Value = serde_json::from_str(&uis).unwrap();
+        let control =
+            |name: &str| json!({"type":"Control","scope":format!("#/properties/{}", name)});
+        assert_eq!(
+            uischema,
+            json!({
+                "type":"VerticalLayout",
+                "elements":[
+                    control("name"),
+                    {
+                        "type":"Group",
+                        "label":"Address",
+                        "elements":[
+                            {
+                                "type":"HorizontalLayout",
+                                "elements":[control("street"), control("number")]
+                            },
+                            control("city")
+                        ]
+                    },
+                    control("id")
+                ]
+            })
+        );
+    }
+
+    #[derive(JsonForms, Deserialize, Debug)]
+    struct TestJsonFormsScopes {
+        #[serde(rename = "a/b")]
+        slash: bool,
+        #[jsonforms(hide_if(field = "slash", equals = true))]
+        #[serde(rename = "c~d")]
+        tilde: String,
+        #[serde(rename = "e/f")]
+        inner: TestJsonFormsRow,
+    }
+
+    #[test]
+    fn jsonforms_scope_escaping() {
+        let (_, uis) = TestJsonFormsScopes::jsonforms_schema();
+        let uischema: serde_json::Value = serde_json::from_str(&uis).unwrap();
+        let elements = &uischema["elements"];
+        assert_eq!(elements[0]["scope"], json!("#/properties/a~1b"));
+        assert_eq!(elements[1]["scope"], json!("#/properties/c~0d"));
+        assert_eq!(
+            elements[1]["rule"]["condition"]["scope"],
+            json!("#/properties/a~1b")
+        );
+        assert_eq!(
+            elements[2]["elements"][0]["elements"][0]["scope"],
+            json!("#/properties/e~1f/properties/name")
+        );
+    }
+
     #[test]
     fn jsonforms001() {
         let (s, uis) = TestJsonForms001::jsonforms_schema();
@@ -1 +1,4 @@
-pub mod json_forms;
\ No newline at end of file
+mod check;
+mod defs;
+mod fields;
+pub mod json_forms;
@@ -0,0 +1,7 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+#[jsonforms(default)]
+struct Point(f64, f64);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+struct Form {
+    #[jsonforms(default = "abc")]
+    port: u16,
+}
+
+fn main() {}
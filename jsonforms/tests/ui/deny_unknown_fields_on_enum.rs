@@ -0,0 +1,10 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+#[jsonforms(deny_unknown_fields)]
+enum Color {
+    Red,
+    Green,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+struct Form {
+    #[jsonforms(max_items = 4)]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+struct Form {
+    #[jsonforms(min_length = 3)]
+    age: Option<u32>,
+}
+
+fn main() {}
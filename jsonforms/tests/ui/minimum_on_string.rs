@@ -0,0 +1,9 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+struct Form {
+    #[jsonforms(minimum = 1)]
+    name: String,
+}
+
+fn main() {}
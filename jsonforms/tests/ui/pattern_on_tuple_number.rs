@@ -0,0 +1,6 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+struct Pair(#[jsonforms(pattern = "^[0-9]+$")] u32, String);
+
+fn main() {}
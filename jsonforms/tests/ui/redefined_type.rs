@@ -0,0 +1,9 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+struct Form {
+    #[jsonforms(schema = r#""type": "integer""#)]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use jsonforms_derive::JsonForms;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonForms)]
+#[serde(tag = "kind")]
+enum Value {
+    Number(f64),
+}
+
+fn main() {}
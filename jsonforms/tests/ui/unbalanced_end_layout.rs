@@ -0,0 +1,11 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+struct Form {
+    #[jsonforms(HorizontalLayout)]
+    first: String,
+    #[jsonforms(EndLayout, EndLayout)]
+    last: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+#[jsonforms(conditional(
+    if(field = "kind", equals = "phone"),
+    then = r#"{"minProperties": 2}"#
+))]
+struct Form {
+    kind: String,
+    contact: String,
+}
+
+fn main() {}
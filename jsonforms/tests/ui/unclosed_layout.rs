@@ -0,0 +1,10 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+struct Form {
+    #[jsonforms(HorizontalLayout)]
+    first: String,
+    last: String,
+}
+
+fn main() {}
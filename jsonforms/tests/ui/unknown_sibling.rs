@@ -0,0 +1,10 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+struct Form {
+    recurrence: String,
+    #[jsonforms(hide_if(field = "recurence", equals = "never"))]
+    interval: u32,
+}
+
+fn main() {}
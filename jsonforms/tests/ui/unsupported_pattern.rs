@@ -0,0 +1,9 @@
+use jsonforms_derive::JsonForms;
+
+#[derive(JsonForms)]
+struct Form {
+    #[jsonforms(pattern = r"^(?=a)\w+$")]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,224 @@
+use super::case::RenameRule;
+use super::symbol::*;
+use syn::{ext::IdentExt, meta::ParseNestedMeta};
+
+/// serde enum representation, see https://serde.rs/enum-representations.html
+pub enum TagType {
+    External,
+    Internal { tag: String },
+    Adjacent { tag: String, content: String },
+    None,
+}
+
+/// serde attributes of the struct or enum deriving JsonForms
+pub struct Container {
+    pub rename_all: RenameRule,
+    /// the default `rename_all` of the struct variants
+    pub rename_all_fields: RenameRule,
+    pub tag: TagType,
+    pub transparent: bool,
+    pub default: bool,
+    pub deny_unknown_fields: bool,
+}
+
+/// serde attributes of an enum variant
+pub struct Variant {
+    pub name: String,
+    pub skip: bool,
+    pub rename_all: RenameRule,
+}
+
+/// serde attributes of a struct field
+pub struct Field {
+    pub name: String,
+    pub skip: bool,
+    pub default: bool,
+    pub flatten: bool,
+}
+
+impl Container {
+    pub fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut rename_all = RenameRule::None;
+        let mut rename_all_fields = RenameRule::None;
+        let mut tag = None;
+        let mut transparent = false;
+        let mut content = None;
+        let mut untagged = false;
+        let mut default = false;
+        let mut deny_unknown_fields = false;
+        for attr in attrs {
+            if attr.path() != SERDE {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path == RENAME_ALL {
+                    if let Some(rule) = get_de_name(&meta)? {
+                        rename_all = RenameRule::parse(&rule.value()).ok_or_else(|| {
+                            syn::Error::new(rule.span(), "unknown serde rename_all rule")
+                        })?;
+                    }
+                } else if meta.path == RENAME_ALL_FIELDS {
+                    if let Some(rule) = get_de_name(&meta)? {
+                        rename_all_fields = RenameRule::parse(&rule.value()).ok_or_else(|| {
+                            syn::Error::new(rule.span(), "unknown serde rename_all_fields rule")
+                        })?;
+                    }
+                } else if meta.path == TRANSPARENT {
+                    transparent = true;
+                } else if meta.path == TAG {
+                    tag = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.path == CONTENT {
+                    content = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.path == UNTAGGED {
+                    untagged = true;
+                } else if meta.path == DEFAULT {
+                    skip_meta(&meta)?;
+                    default = true;
+                } else if meta.path == DENY_UNKNOWN_FIELDS {
+                    deny_unknown_fields = true;
+                } else {
+                    skip_meta(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        let tag = match (untagged, tag, content) {
+            (true, _, _) => TagType::None,
+            (false, Some(tag), Some(content)) => TagType::Adjacent { tag, content },
+            (false, Some(tag), None) => TagType::Internal { tag },
+            (false, None, _) => TagType::External,
+        };
+        Ok(Self {
+            rename_all,
+            rename_all_fields,
+            tag,
+            transparent,
+            default,
+            deny_unknown_fields,
+        })
+    }
+
+    /// struct variant fields follow the variant `rename_all`
+    pub fn from_variant(variant: &Variant) -> Self {
+        Self {
+            rename_all: variant.rename_all,
+            rename_all_fields: RenameRule::None,
+            tag: TagType::External,
+            transparent: false,
+            default: false,
+            deny_unknown_fields: false,
+        }
+    }
+}
+
+impl Variant {
+    pub fn from_ast(variant: &syn::Variant, container: &Container) -> syn::Result<Self> {
+        let mut name = None;
+        let mut skip = false;
+        let mut rename_all = container.rename_all_fields;
+        for attr in &variant.attrs {
+            if attr.path() != SERDE {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path == RENAME {
+                    if let Some(rename) = get_de_name(&meta)? {
+                        name = Some(rename.value());
+                    }
+                } else if meta.path == RENAME_ALL {
+                    if let Some(rule) = get_de_name(&meta)? {
+                        rename_all = RenameRule::parse(&rule.value()).ok_or_else(|| {
+                            syn::Error::new(rule.span(), "unknown serde rename_all rule")
+                        })?;
+                    }
+                } else if meta.path == SERDE_SKIP || meta.path == SKIP_DESERIALIZING {
+                    skip = true;
+                } else {
+                    skip_meta(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        let name = name.unwrap_or_else(|| {
+            container
+                .rename_all
+                .apply_to_variant(&variant.ident.unraw().to_string())
+        });
+        Ok(Self {
+            name,
+            skip,
+            rename_all,
+        })
+    }
+}
+
+impl Field {
+    pub fn from_ast(field: &syn::Field, container: &Container) -> syn::Result<Self> {
+        let mut name = None;
+        let mut skip = false;
+        let mut default = container.default;
+        let mut flatten = false;
+        for attr in &field.attrs {
+            if attr.path() != SERDE {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path == RENAME {
+                    if let Some(rename) = get_de_name(&meta)? {
+                        name = Some(rename.value());
+                    }
+                } else if meta.path == SERDE_SKIP || meta.path == SKIP_DESERIALIZING {
+                    skip = true;
+                } else if meta.path == DEFAULT {
+                    skip_meta(&meta)?;
+                    default = true;
+                } else if meta.path == FLATTEN {
+                    flatten = true;
+                } else {
+                    skip_meta(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        let name = name.unwrap_or_else(|| match &field.ident {
+            Some(ident) => container
+                .rename_all
+                .apply_to_field(&ident.unraw().to_string()),
+            None => String::new(),
+        });
+        Ok(Self {
+            name,
+            skip,
+            default,
+            flatten,
+        })
+    }
+}
+
+/// `rename = "name"` or `rename(deserialize = "name")`, the deserialize name is
+/// the one the client must send back
+fn get_de_name(meta: &ParseNestedMeta) -> syn::Result<Option<syn::LitStr>> {
+    if meta.input.peek(Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+    let mut de_name = None;
+    meta.parse_nested_meta(|meta| {
+        if meta.path == DESERIALIZE {
+            de_name = Some(meta.value()?.parse()?);
+        } else {
+            skip_meta(&meta)?;
+        }
+        Ok(())
+    })?;
+    Ok(de_name)
+}
+
+/// consume the value of an attribute the derive does not care about
+pub fn skip_meta(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|meta| skip_meta(&meta))?;
+    }
+    Ok(())
+}
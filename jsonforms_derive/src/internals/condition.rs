@@ -0,0 +1,204 @@
+use super::symbol::*;
+use crate::ser::{get_json_literal, get_lit_str, json_str, scope_token, OutTokens};
+use itertools::join;
+use proc_macro2::TokenStream;
+use syn::{ext::IdentExt, meta::ParseNestedMeta};
+
+/// a named field other attributes of the struct can refer to
+pub struct Sibling<'a> {
+    pub ident: &'a syn::Ident,
+    pub name: String,
+    pub is_option: bool,
+}
+
+/// the field named `lit`, by its rust or serde name
+pub fn find_sibling<'s, 'a>(
+    siblings: &'s [Sibling<'a>],
+    lit: &syn::LitStr,
+) -> syn::Result<&'s Sibling<'a>> {
+    let value = lit.value();
+    siblings
+        .iter()
+        .find(|sibling| sibling.ident.unraw() == value || sibling.name == value)
+        .ok_or_else(|| syn::Error::new(lit.span(), format!("unknown field `{}`", value)))
+}
+
+/// condition on the data of sibling fields, of a JSON Forms rule or a schema `if`
+pub enum Condition {
+    /// the field, by serde name, is valid against the schema. `equals` is the
+    /// json of an `equals` condition, the only kind the server can check
+    Field {
+        name: String,
+        ident: syn::Ident,
+        schema: String,
+        equals: Option<String>,
+    },
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+}
+
+impl Condition {
+    /// schema the form data is valid against when the condition holds
+    pub fn to_schema(&self) -> String {
+        match self {
+            Condition::Field { name, schema, .. } => {
+                let name = json_str(name);
+                format!(
+                    r#"{{"properties":{{{}:{}}},"required":[{}]}}"#,
+                    name, schema, name
+                )
+            }
+            Condition::And(conditions) => format!(
+                r#"{{"allOf":[{}]}}"#,
+                join(conditions.iter().map(Condition::to_schema), ",")
+            ),
+            Condition::Or(conditions) => format!(
+                r#"{{"anyOf":[{}]}}"#,
+                join(conditions.iter().map(Condition::to_schema), ",")
+            ),
+        }
+    }
+
+    /// the condition on the bound fields, for the server side check
+    pub fn to_check(&self) -> syn::Result<TokenStream> {
+        match self {
+            Condition::Field {
+                ident,
+                equals: Some(equals),
+                ..
+            } => Ok(quote!(jsonforms_equals(#ident, #equals))),
+            Condition::Field { ident, .. } => Err(syn::Error::new(
+                ident.span(),
+                "only `equals` conditions can be checked by the server",
+            )),
+            Condition::And(conditions) => {
+                let checks = conditions
+                    .iter()
+                    .map(Condition::to_check)
+                    .collect::<syn::Result<Vec<_>>>()?;
+                Ok(quote!((#(#checks)&&*)))
+            }
+            Condition::Or(conditions) => {
+                let checks = conditions
+                    .iter()
+                    .map(Condition::to_check)
+                    .collect::<syn::Result<Vec<_>>>()?;
+                Ok(quote!((#(#checks)||*)))
+            }
+        }
+    }
+}
+
+/// `field = "name", equals = value`, `field = "name", schema = "{..}"`,
+/// `all(..)` or `any(..)`
+pub fn parse_condition(meta: &ParseNestedMeta, siblings: &[Sibling]) -> syn::Result<Condition> {
+    let mut field = None;
+    let mut schema = None;
+    let mut equals = None;
+    let mut composed = None;
+    meta.parse_nested_meta(|meta| {
+        if meta.path == FIELD {
+            let lit = get_lit_str(&meta)?;
+            let sibling = find_sibling(siblings, &lit)?;
+            // spanned on the attribute, so errors point at the condition
+            let mut ident = sibling.ident.clone();
+            ident.set_span(lit.span());
+            field = Some((sibling.name.clone(), ident));
+        } else if meta.path == EQUALS || meta.path == SCHEMA {
+            if schema.is_some() {
+                return Err(meta.error("use either `equals` or `schema`"));
+            }
+            if meta.path == EQUALS {
+                let value = get_json_literal(&meta)?;
+                schema = Some(format!(r#"{{"const":{}}}"#, value));
+                equals = Some(value);
+            } else {
+                let lit = get_lit_str(&meta)?;
+                if !serde_json::from_str::<serde_json::Value>(&lit.value())
+                    .is_ok_and(|value| value.is_object())
+                {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "condition schema must be a json object",
+                    ));
+                }
+                schema = Some(lit.value());
+            }
+        } else if meta.path == ALL {
+            composed = Some(Condition::And(parse_conditions(&meta, siblings)?));
+        } else if meta.path == ANY {
+            composed = Some(Condition::Or(parse_conditions(&meta, siblings)?));
+        } else {
+            return Err(meta.error("unknown jsonforms condition attribute"));
+        }
+        Ok(())
+    })?;
+    match (composed, field, schema) {
+        (Some(condition), None, None) => Ok(condition),
+        (None, Some((name, ident)), Some(schema)) => Ok(Condition::Field {
+            name,
+            ident,
+            schema,
+            equals,
+        }),
+        _ => Err(meta
+            .error("expected `field` with `equals` or `schema`, or one of `all(..)`, `any(..)`")),
+    }
+}
+
+/// `when(..)`, `all(..)` and `any(..)` conditions of an `all` or `any`
+fn parse_conditions(meta: &ParseNestedMeta, siblings: &[Sibling]) -> syn::Result<Vec<Condition>> {
+    let mut conditions = Vec::new();
+    meta.parse_nested_meta(|meta| {
+        if meta.path == WHEN {
+            conditions.push(parse_condition(&meta, siblings)?);
+        } else if meta.path == ALL {
+            conditions.push(Condition::And(parse_conditions(&meta, siblings)?));
+        } else if meta.path == ANY {
+            conditions.push(Condition::Or(parse_conditions(&meta, siblings)?));
+        } else {
+            return Err(meta.error("expected `when(..)`, `all(..)` or `any(..)`"));
+        }
+        Ok(())
+    })?;
+    if conditions.is_empty() {
+        return Err(meta.error("expected at least one condition"));
+    }
+    Ok(conditions)
+}
+
+/// the `"rule"` member, condition scopes are relative to the runtime `scope`
+pub fn render_rule(uiout: &mut OutTokens, effect: &str, condition: &Condition) {
+    uiout.push_str(&format!(r#""rule":{{"effect":"{}","condition":"#, effect));
+    render_condition(uiout, condition);
+    uiout.push_str("}");
+}
+
+fn render_condition(uiout: &mut OutTokens, condition: &Condition) {
+    match condition {
+        Condition::Field { name, schema, .. } => {
+            uiout.push_str(r#"{"scope":""#);
+            uiout.push_tokens(quote!(uiout_str += scope;));
+            uiout.push_str(&format!(
+                r#"/properties/{}","schema":{}}}"#,
+                scope_token(name),
+                schema
+            ));
+        }
+        Condition::And(conditions) | Condition::Or(conditions) => {
+            let kind = if matches!(condition, Condition::And(_)) {
+                "AND"
+            } else {
+                "OR"
+            };
+            uiout.push_str(&format!(r#"{{"type":"{}","conditions":["#, kind));
+            for (i, condition) in conditions.iter().enumerate() {
+                if i > 0 {
+                    uiout.push_str(",");
+                }
+                render_condition(uiout, condition);
+            }
+            uiout.push_str("]}");
+        }
+    }
+}
@@ -0,0 +1,380 @@
+use super::attr;
+use super::symbol::*;
+use crate::ser::{get_json_str, get_lit_str, get_value_expr, json_number, OutTokens};
+use proc_macro2::{Span, TokenStream};
+use syn::{meta::ParseNestedMeta, spanned::Spanned};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LayoutKind {
+    Layout,
+    Categorization,
+    Category,
+}
+
+/// a layout opened by a field marker and closed by `EndLayout`, or declared
+/// by name at struct level
+pub struct UiLayout {
+    pub kind: LayoutKind,
+    /// members before `"elements"`, e.g. `"type":"Group","label":"Address"`
+    pub head: String,
+    /// the layout is sorted with its siblings by `order`
+    pub order: i64,
+    pub elements: Vec<(i64, UiNode)>,
+}
+
+pub enum UiNode {
+    Element(TokenStream),
+    Layout(UiLayout),
+    /// a named layout, placed where its first field goes
+    Named(usize),
+}
+
+/// a layout declared at struct level, fields refer to it by name
+struct NamedLayout {
+    name: String,
+    parent: Option<usize>,
+    layout: Option<UiLayout>,
+    placed: bool,
+    span: Span,
+}
+
+/// uischema elements of a struct, nested in the layouts of the field markers
+/// and the named layouts. Elements are sorted by their `order`, 0 by default,
+/// and keep the field order otherwise
+#[derive(Default)]
+pub struct UiLayouts {
+    root: Vec<(i64, UiNode)>,
+    /// with the span of the marker that opened them
+    open: Vec<(UiLayout, Span)>,
+    named: Vec<NamedLayout>,
+}
+
+impl UiLayouts {
+    fn in_categorization(&self) -> bool {
+        matches!(self.open.last(), Some((layout, _)) if layout.kind == LayoutKind::Categorization)
+    }
+
+    pub fn open(
+        &mut self,
+        kind: LayoutKind,
+        head: String,
+        order: i64,
+        span: Span,
+    ) -> syn::Result<()> {
+        if kind == LayoutKind::Category && !self.in_categorization() {
+            return Err(syn::Error::new(
+                span,
+                "Category must be directly inside a Categorization",
+            ));
+        }
+        if kind != LayoutKind::Category && self.in_categorization() {
+            return Err(syn::Error::new(
+                span,
+                "a Categorization can only contain Category layouts",
+            ));
+        }
+        self.open.push((
+            UiLayout {
+                kind,
+                head,
+                order,
+                elements: Vec::new(),
+            },
+            span,
+        ));
+        Ok(())
+    }
+
+    pub fn close(&mut self, span: Span) -> syn::Result<()> {
+        let (layout, _) = self
+            .open
+            .pop()
+            .ok_or_else(|| syn::Error::new(span, "EndLayout without an open layout"))?;
+        self.push(layout.order, UiNode::Layout(layout));
+        Ok(())
+    }
+
+    pub fn push_element(
+        &mut self,
+        element: TokenStream,
+        order: i64,
+        span: Span,
+    ) -> syn::Result<()> {
+        if self.in_categorization() {
+            return Err(syn::Error::new(
+                span,
+                "a Categorization can only contain Category layouts, open one for this field",
+            ));
+        }
+        self.push(order, UiNode::Element(element));
+        Ok(())
+    }
+
+    fn push(&mut self, order: i64, node: UiNode) {
+        match self.open.last_mut() {
+            Some((layout, _)) => layout.elements.push((order, node)),
+            None => self.root.push((order, node)),
+        }
+    }
+
+    /// `parent` is a named layout declared before this one
+    fn declare(
+        &mut self,
+        name: String,
+        parent: Option<(String, Span)>,
+        layout: UiLayout,
+        span: Span,
+    ) -> syn::Result<()> {
+        if self.named.iter().any(|named| named.name == name) {
+            return Err(syn::Error::new(
+                span,
+                format!("layout `{}` is already declared", name),
+            ));
+        }
+        let parent = match parent {
+            Some((parent, parent_span)) => Some(self.find_named(&parent, parent_span)?),
+            None => None,
+        };
+        let in_categorization = matches!(
+            parent.and_then(|parent| self.named[parent].layout.as_ref()),
+            Some(parent) if parent.kind == LayoutKind::Categorization
+        );
+        if layout.kind == LayoutKind::Category && !in_categorization {
+            return Err(syn::Error::new(
+                span,
+                "Category must be directly inside a Categorization",
+            ));
+        }
+        if layout.kind != LayoutKind::Category && in_categorization {
+            return Err(syn::Error::new(
+                span,
+                "a Categorization can only contain Category layouts",
+            ));
+        }
+        self.named.push(NamedLayout {
+            name,
+            parent,
+            layout: Some(layout),
+            placed: false,
+            span,
+        });
+        Ok(())
+    }
+
+    fn find_named(&self, name: &str, span: Span) -> syn::Result<usize> {
+        self.named
+            .iter()
+            .position(|named| named.name == name)
+            .ok_or_else(|| syn::Error::new(span, format!("unknown layout `{}`", name)))
+    }
+
+    /// the element goes in the layout `name` whatever the open field markers
+    pub fn push_named(
+        &mut self,
+        name: &str,
+        element: TokenStream,
+        order: i64,
+        span: Span,
+    ) -> syn::Result<()> {
+        let index = self.find_named(name, span)?;
+        let layout = self.named[index].layout.as_mut().expect("named layout");
+        if layout.kind == LayoutKind::Categorization {
+            return Err(syn::Error::new(
+                span,
+                "a Categorization can only contain Category layouts, place this field in one",
+            ));
+        }
+        layout.elements.push((order, UiNode::Element(element)));
+        self.place(index);
+        Ok(())
+    }
+
+    /// adds a named layout to its parent on first use
+    fn place(&mut self, index: usize) {
+        if self.named[index].placed {
+            return;
+        }
+        self.named[index].placed = true;
+        let order = self.named[index]
+            .layout
+            .as_ref()
+            .map_or(0, |layout| layout.order);
+        match self.named[index].parent {
+            Some(parent) => {
+                if let Some(layout) = self.named[parent].layout.as_mut() {
+                    layout.elements.push((order, UiNode::Named(index)));
+                }
+                self.place(parent);
+            }
+            None => self.root.push((order, UiNode::Named(index))),
+        }
+    }
+
+    /// every layout opened by a field marker is closed by an `EndLayout`
+    pub fn unclosed(&self) -> Vec<syn::Error> {
+        self.open
+            .iter()
+            .map(|(_, span)| syn::Error::new(*span, "layout without an EndLayout"))
+            .collect()
+    }
+
+    /// named layouts without any field are likely a typo
+    pub fn unused_named(&self) -> Vec<syn::Error> {
+        self.named
+            .iter()
+            .filter(|named| !named.placed)
+            .map(|named| {
+                syn::Error::new(named.span, format!("layout `{}` has no fields", named.name))
+            })
+            .collect()
+    }
+
+    /// named layouts put in place of their first use, every level sorted
+    fn resolve(&mut self, mut nodes: Vec<(i64, UiNode)>) -> Vec<(i64, UiNode)> {
+        nodes.sort_by_key(|(order, _)| *order);
+        nodes
+            .into_iter()
+            .filter_map(|(order, node)| {
+                let mut layout = match node {
+                    UiNode::Element(_) => return Some((order, node)),
+                    UiNode::Layout(layout) => layout,
+                    UiNode::Named(index) => self.named[index].layout.take()?,
+                };
+                let elements = std::mem::take(&mut layout.elements);
+                layout.elements = self.resolve(elements);
+                Some((order, UiNode::Layout(layout)))
+            })
+            .collect()
+    }
+
+    /// a single top layout is the root element, otherwise the elements are
+    /// wrapped in a VerticalLayout
+    pub fn into_tokens(mut self) -> TokenStream {
+        let root = std::mem::take(&mut self.root);
+        let mut root = self.resolve(root);
+        let root = match (root.len(), root.pop()) {
+            (1, Some((_, UiNode::Layout(layout)))) => layout,
+            (_, last) => {
+                root.extend(last);
+                UiLayout {
+                    kind: LayoutKind::Layout,
+                    head: r#""type":"VerticalLayout""#.to_owned(),
+                    order: 0,
+                    elements: root,
+                }
+            }
+        };
+        let mut uiout = OutTokens::new("uiout_str");
+        render_layout(&mut uiout, root);
+        uiout.into_tokens()
+    }
+}
+
+fn render_layout(uiout: &mut OutTokens, layout: UiLayout) {
+    uiout.push_str("{");
+    uiout.push_str(&layout.head);
+    uiout.push_str(r#","elements":["#);
+    for (i, (_, node)) in layout.elements.into_iter().enumerate() {
+        if i > 0 {
+            uiout.push_str(",");
+        }
+        match node {
+            UiNode::Element(element) => uiout.push_tokens(element),
+            UiNode::Layout(layout) => render_layout(uiout, layout),
+            UiNode::Named(_) => (),
+        }
+    }
+    uiout.push_str("]}");
+}
+
+/// `HorizontalLayout`, `VerticalLayout`, `Group [= "label"]`,
+/// `Categorization` or `Category = "label"`
+pub fn parse_layout_marker(meta: &ParseNestedMeta) -> syn::Result<Option<(LayoutKind, String)>> {
+    let marker = if meta.path == HLAYOUT {
+        (
+            LayoutKind::Layout,
+            r#""type":"HorizontalLayout""#.to_owned(),
+        )
+    } else if meta.path == VLAYOUT {
+        (LayoutKind::Layout, r#""type":"VerticalLayout""#.to_owned())
+    } else if meta.path == GROUP {
+        let head = if meta.input.peek(Token![=]) {
+            format!(r#""type":"Group","label":{}"#, get_json_str(meta)?)
+        } else {
+            r#""type":"Group""#.to_owned()
+        };
+        (LayoutKind::Layout, head)
+    } else if meta.path == CATEGORIZATION {
+        (
+            LayoutKind::Categorization,
+            r#""type":"Categorization""#.to_owned(),
+        )
+    } else if meta.path == CATEGORY {
+        let head = format!(r#""type":"Category","label":{}"#, get_json_str(meta)?);
+        (LayoutKind::Category, head)
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(marker))
+}
+
+/// struct level `layout(name = "address", Group = "Address")` declarations,
+/// with an optional `layout = "parent"` and `order = N`
+pub fn parse_named_layouts(attrs: &[syn::Attribute], layouts: &mut UiLayouts) -> syn::Result<()> {
+    for attr in attrs {
+        if attr.path() != JSONFORMS {
+            continue;
+        }
+
+        if let syn::Meta::List(meta) = &attr.meta {
+            if meta.tokens.is_empty() {
+                continue;
+            }
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path != LAYOUT {
+                return attr::skip_meta(&meta);
+            }
+            let mut name = None;
+            let mut marker = None;
+            let mut parent = None;
+            let mut order = 0;
+            meta.parse_nested_meta(|meta| {
+                if meta.path == NAME {
+                    name = Some(get_lit_str(&meta)?.value());
+                } else if meta.path == LAYOUT {
+                    let lit = get_lit_str(&meta)?;
+                    parent = Some((lit.value(), lit.span()));
+                } else if meta.path == ORDER {
+                    order = get_order(&meta)?;
+                } else if let Some(found) = parse_layout_marker(&meta)? {
+                    marker = Some(found);
+                } else {
+                    return Err(meta.error("unknown jsonforms layout attribute"));
+                }
+                Ok(())
+            })?;
+            let span = meta.path.span();
+            let name = name.ok_or_else(|| meta.error("layout needs a `name`"))?;
+            let (kind, head) =
+                marker.unwrap_or((LayoutKind::Layout, r#""type":"VerticalLayout""#.to_owned()));
+            let layout = UiLayout {
+                kind,
+                head,
+                order,
+                elements: Vec::new(),
+            };
+            layouts.declare(name, parent, layout, span)
+        })?;
+    }
+    Ok(())
+}
+
+/// `order = N`, fields are sorted by it in their layout
+pub fn get_order(meta: &ParseNestedMeta) -> syn::Result<i64> {
+    let expr = get_value_expr(meta)?;
+    json_number(&expr)?
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| syn::Error::new(expr.span(), "expected integer for `order`"))
+}
@@ -1 +1,5 @@
-pub mod symbol;
\ No newline at end of file
+pub mod attr;
+pub mod case;
+pub mod condition;
+pub mod layout;
+pub mod symbol;
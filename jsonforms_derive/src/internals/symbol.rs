@@ -9,9 +9,64 @@ pub const SKIP: Symbol = Symbol("Skip");
 pub const VLAYOUT: Symbol = Symbol("VerticalLayout");
 pub const HLAYOUT: Symbol = Symbol("HorizontalLayout");
 pub const ELAYOUT: Symbol = Symbol("EndLayout");
+pub const GROUP: Symbol = Symbol("Group");
+pub const CATEGORIZATION: Symbol = Symbol("Categorization");
+pub const CATEGORY: Symbol = Symbol("Category");
 pub const SCHEMA: Symbol = Symbol("schema");
 pub const UISCHEMA: Symbol = Symbol("uischema");
 pub const DEBUG: Symbol = Symbol("debug");
+pub const TITLE: Symbol = Symbol("title");
+pub const DESCRIPTION: Symbol = Symbol("description");
+pub const ID: Symbol = Symbol("id");
+pub const DENY_UNKNOWN_FIELDS: Symbol = Symbol("deny_unknown_fields");
+pub const MIN_LENGTH: Symbol = Symbol("min_length");
+pub const MAX_LENGTH: Symbol = Symbol("max_length");
+pub const PATTERN: Symbol = Symbol("pattern");
+pub const MINIMUM: Symbol = Symbol("minimum");
+pub const MAXIMUM: Symbol = Symbol("maximum");
+pub const FORMAT: Symbol = Symbol("format");
+pub const LABEL: Symbol = Symbol("label");
+pub const MULTI: Symbol = Symbol("multi");
+pub const READONLY: Symbol = Symbol("readonly");
+pub const MIN_ITEMS: Symbol = Symbol("min_items");
+pub const MAX_ITEMS: Symbol = Symbol("max_items");
+pub const SHOW_SORT_BUTTONS: Symbol = Symbol("show_sort_buttons");
+pub const ELEMENT_LABEL_PROP: Symbol = Symbol("element_label_prop");
+pub const ARRAY: Symbol = Symbol("array");
+pub const ORDER: Symbol = Symbol("order");
+pub const LAYOUT: Symbol = Symbol("layout");
+pub const NAME: Symbol = Symbol("name");
+pub const HIDE_IF: Symbol = Symbol("hide_if");
+pub const SHOW_IF: Symbol = Symbol("show_if");
+pub const ENABLE_IF: Symbol = Symbol("enable_if");
+pub const DISABLE_IF: Symbol = Symbol("disable_if");
+pub const FIELD: Symbol = Symbol("field");
+pub const EQUALS: Symbol = Symbol("equals");
+pub const WHEN: Symbol = Symbol("when");
+pub const ALL: Symbol = Symbol("all");
+pub const ANY: Symbol = Symbol("any");
+pub const MUST_EQUAL: Symbol = Symbol("must_equal");
+pub const REQUIRED_IF: Symbol = Symbol("required_if");
+pub const DEPENDENT_REQUIRED: Symbol = Symbol("dependent_required");
+pub const CONDITIONAL: Symbol = Symbol("conditional");
+pub const IF: Symbol = Symbol("if");
+pub const THEN: Symbol = Symbol("then");
+pub const ELSE: Symbol = Symbol("else");
+pub const VALIDATE_WITH: Symbol = Symbol("validate_with");
+
+pub const SERDE: Symbol = Symbol("serde");
+pub const RENAME: Symbol = Symbol("rename");
+pub const RENAME_ALL: Symbol = Symbol("rename_all");
+pub const RENAME_ALL_FIELDS: Symbol = Symbol("rename_all_fields");
+pub const DESERIALIZE: Symbol = Symbol("deserialize");
+pub const SERDE_SKIP: Symbol = Symbol("skip");
+pub const SKIP_DESERIALIZING: Symbol = Symbol("skip_deserializing");
+pub const TAG: Symbol = Symbol("tag");
+pub const CONTENT: Symbol = Symbol("content");
+pub const UNTAGGED: Symbol = Symbol("untagged");
+pub const DEFAULT: Symbol = Symbol("default");
+pub const FLATTEN: Symbol = Symbol("flatten");
+pub const TRANSPARENT: Symbol = Symbol("transparent");
 
 impl PartialEq<Symbol> for Ident {
     fn eq(&self, word: &Symbol) -> bool {
@@ -19,7 +74,7 @@ impl PartialEq<Symbol> for Ident {
     }
 }
 
-impl<'a> PartialEq<Symbol> for &'a Ident {
+impl PartialEq<Symbol> for &Ident {
     fn eq(&self, word: &Symbol) -> bool {
         *self == word.0
     }
@@ -31,7 +86,7 @@ impl PartialEq<Symbol> for Path {
     }
 }
 
-impl<'a> PartialEq<Symbol> for &'a Path {
+impl PartialEq<Symbol> for &Path {
     fn eq(&self, word: &Symbol) -> bool {
         self.is_ident(word.0)
     }
@@ -41,4 +96,4 @@ impl Display for Symbol {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.write_str(self.0)
     }
-}
\ No newline at end of file
+}
@@ -8,8 +8,8 @@ extern crate proc_macro;
 extern crate proc_macro2;
 
 use syn::DeriveInput;
-mod ser;
 mod internals;
+mod ser;
 
 #[proc_macro_derive(JsonForms, attributes(jsonforms))]
 pub fn derive_serialize(input: TokenStream) -> TokenStream {
@@ -19,7 +19,12 @@ pub fn derive_serialize(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// every message starts with `jsonforms: `, the error keeps its whole span
+/// through the tokens of its compile_error
 fn to_compile_errors(errors: Vec<syn::Error>) -> proc_macro2::TokenStream {
-    let compile_errors = errors.iter().map(syn::Error::to_compile_error);
+    let compile_errors = errors.into_iter().flatten().map(|err| {
+        let message = format!("jsonforms: {}", err);
+        syn::Error::new_spanned(err.to_compile_error(), message).to_compile_error()
+    });
     quote!(#(#compile_errors)*)
 }
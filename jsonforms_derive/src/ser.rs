@@ -1,113 +1,400 @@
-use crate::internals::symbol::*;
+use crate::internals::{
+    attr,
+    condition::{find_sibling, parse_condition, render_rule, Condition, Sibling},
+    layout::{get_order, parse_layout_marker, parse_named_layouts, LayoutKind, UiLayouts},
+    symbol::*,
+};
 use itertools::join;
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use syn::{meta::ParseNestedMeta, spanned::Spanned, DeriveInput};
 
 pub(crate) fn expand_derive_jsonforms(
     input: &mut DeriveInput,
 ) -> Result<TokenStream, Vec<syn::Error>> {
     let ident = &input.ident;
-    let mut debug = false;
-    let attrs = &input.attrs;
-    for attr in attrs {
-        if attr.path() != JSONFORMS {
-            continue;
+    let meta = FormMeta::from_attrs(&input.attrs).map_err(|err| vec![err])?;
+    let (props, uiprops, checks) = expand_props(input)?;
+    // `$id` only on the root schema, in a definition it would change how
+    // the `$ref` inside are resolved
+    let schema_id = match &meta.id {
+        Some(id) => {
+            let member = format!(r#""$id":{}"#, json_str(id));
+            quote!(let out_str = {
+                let mut schema = String::new();
+                jsonforms_extend(&mut schema, &out_str, #member);
+                schema
+            };)
         }
-
-        if let syn::Meta::List(meta) = &attr.meta {
-            if meta.tokens.is_empty() {
-                continue;
+        None => quote!(),
+    };
+    // constraints the derive checks on submitted data
+    let check = if checks.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            #[allow(unused_variables)]
+            fn jsonforms_check(
+                &self,
+                jsonforms_pointer: &str,
+                jsonforms_errors: &mut Vec<JsonFormsError>,
+            ) {
+                #checks
             }
         }
+    };
 
-        if let Err(err) = attr.parse_nested_meta(|meta| {
-            if meta.path == DEBUG {
-                debug = true;
+    // structs used as fields of another form are rendered as a Group
+    let group = match (&input.data, newtype_field(input)) {
+        // a newtype is rendered like its field, other tuple structs are a Control
+        (_, Some((f, _))) => match get_type(&f.ty).ref_type {
+            Some(ref_type) => quote! {
+                const JSONFORMS_LAYOUT: bool = <#ref_type as JsonFormsField>::JSONFORMS_LAYOUT;
+                fn jsonforms_uischema(scope: &str, extra: &str) -> String {
+                    <#ref_type as JsonFormsField>::jsonforms_field_uischema(scope, extra)
+                }
+            },
+            None => quote!(),
+        },
+        (
+            syn::Data::Struct(syn::DataStruct {
+                fields: syn::Fields::Unnamed(_),
+                ..
+            }),
+            None,
+        ) => quote!(),
+        (syn::Data::Struct(_), None) => quote! {
+            const JSONFORMS_LAYOUT: bool = true;
+            #[allow(unused_variables)]
+            fn jsonforms_uischema(scope: &str, extra: &str) -> String {
+                jsonforms_group::<Self>(scope, extra, || {
+                    let mut uiout_str = String::from(r#"{"type":"Group""#);
+                    if !extra.is_empty() {
+                        uiout_str += ",";
+                        uiout_str += extra;
+                    }
+                    uiout_str += r#","elements":["#;
+                    #uiprops
+                    uiout_str += "]}";
+                    uiout_str
+                })
             }
-            Ok(())
-        }) {
-            return Err(vec![syn::Error::new(
-                input.span(),
-                format!(
-                    "expand_derive_jsonforms: invalid meta parse for jsonforms attribute {:?}",
-                    err
-                ),
-            )]);
-        }
+        },
+        _ => quote!(),
+    };
+
+    let view = expand_view(input)?;
+
+    // type parameters need a schema, like serde bounds them by Deserialize
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(JsonFormsField));
     }
-    let scope = String::from("#/properties");
-    let (props, uiprops) = expand_props(&input.data, input.span(), scope)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    // a static in a generic impl is shared by all its instantiations, those
+    // keep the default cache keyed by type
+    let static_schema = if generics.type_params().next().is_none()
+        && generics.const_params().next().is_none()
+    {
+        quote! {
+            fn jsonforms_static() -> &'static JsonFormsSchema {
+                static SCHEMA: std::sync::OnceLock<JsonFormsSchema> = std::sync::OnceLock::new();
+                SCHEMA.get_or_init(|| JsonFormsSchema::new(Self::jsonforms_schema()))
+            }
+        }
+    } else {
+        quote!()
+    };
 
     let quote = quote! {
-        impl JsonFormsSerializable for #ident {
-            fn jsonforms_schema() -> (String,String) {
+        impl #impl_generics JsonFormsSerializable for #ident #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn jsonforms_definition(defs: &mut JsonFormsDefs) -> String {
                 let mut out_str = String::new();
                 #props
+                out_str
+            }
+            #[allow(unused_variables)]
+            fn jsonforms_schema() -> (String,String) {
+                let mut defs = JsonFormsDefs::new();
+                let out_str = Self::jsonforms_definition(&mut defs);
+                let out_str = defs.schema(out_str);
+                #schema_id
+                let scope = "#";
                 let mut uiout_str = String::new();
                 #uiprops
                 (out_str,uiout_str)
             }
+            #static_schema
+            #group
+            #check
+            #view
         }
     };
-    if debug {
+    if meta.debug {
         println!("{} derive JsonForms\n\n{}\n\n", ident, quote);
     }
     Ok(quote)
 }
 
+/// schema, uischema and server side checks of the type
 fn expand_props(
-    data: &syn::Data,
-    span: Span,
-    scope: String,
-) -> Result<(TokenStream, TokenStream), Vec<syn::Error>> {
+    input: &DeriveInput,
+) -> Result<(TokenStream, TokenStream, TokenStream), Vec<syn::Error>> {
     let out;
     let uiout;
-    match data {
+    let checks;
+    match &input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: fields @ syn::Fields::Unnamed(_),
+            ..
+        }) => {
+            needs_named_fields(&input.attrs)?;
+            (out, uiout, checks) = expand_tuple(fields, &input.attrs)?;
+        }
+        // serde (de)serializes a unit struct as null
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Unit,
+            ..
+        }) => {
+            needs_named_fields(&input.attrs)?;
+            let meta = FormMeta::from_attrs(&input.attrs).map_err(|err| vec![err])?;
+            let schema = format!(r#"{{{}"type":"null"}}"#, meta.head());
+            out = quote!(out_str += #schema;);
+            uiout = quote!(uiout_str += &jsonforms_control(scope, ""););
+            let mut hooks = TokenStream::new();
+            parse_struct_checks(
+                &input.attrs,
+                &[],
+                &mut Vec::new(),
+                &mut TokenStream::new(),
+                &mut hooks,
+            )
+            .map_err(|err| vec![err])?;
+            checks = hooks;
+        }
         syn::Data::Struct(s) => {
-            let (outs, uiouts) = expand_struct(s, scope)?;
-            out = outs;
+            let mut container =
+                attr::Container::from_attrs(&input.attrs).map_err(|err| vec![err])?;
+            // serde (de)serializes a transparent struct as its only field
+            if container.transparent {
+                needs_named_fields(&input.attrs)?;
+                if s.fields.len() != 1 {
+                    return Err(vec![syn::Error::new(
+                        input.ident.span(),
+                        "serde transparent needs a struct with exactly one field",
+                    )]);
+                }
+                return expand_tuple(&s.fields, &input.attrs);
+            }
+            let meta = FormMeta::from_attrs(&input.attrs).map_err(|err| vec![err])?;
+            container.deny_unknown_fields |= meta.deny_unknown_fields.is_some();
+            let head = meta.head();
+            let (outs, uiouts, struct_checks) = expand_struct(
+                &s.fields,
+                &head,
+                None,
+                &container,
+                meta.default.is_some(),
+                &input.attrs,
+            )?;
+            // field defaults are serialized from the Default impl
+            out = if meta.default.is_some() {
+                quote!(
+                    let jsonforms_default_value = <Self as Default>::default();
+                    #outs
+                )
+            } else {
+                outs
+            };
             uiout = uiouts;
+            checks = bind_fields(&s.fields, struct_checks);
         }
-        syn::Data::Enum(_) => {
-            return Err(vec![syn::Error::new(
-                span,
-                "expand_props: Enum not supported",
-            )]);
+        syn::Data::Enum(e) => {
+            needs_named_fields(&input.attrs)?;
+            let (outs, uiouts, arms) = expand_enum(e, &input.attrs)?;
+            out = outs;
+            uiout = uiouts;
+            checks = if arms.is_empty() {
+                arms
+            } else {
+                quote!(
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        #arms
+                        _ => {}
+                    }
+                )
+            };
         }
         syn::Data::Union(_) => {
             return Err(vec![syn::Error::new(
-                span,
-                "expand_props: Union not supported",
+                input.span(),
+                "unions are not supported",
             )]);
         }
     }
-    Ok((out, uiout))
+    Ok((out, uiout, checks))
 }
 
+/// the value seen by the constraint keywords of the fields using the type,
+/// the one of its field for a newtype, null for a unit struct and the name of
+/// an externally tagged unit variant
+fn expand_view(input: &DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
+    let view = match &input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Unit,
+            ..
+        }) => quote!(JsonFormsValue::Null),
+        syn::Data::Struct(_) => match newtype_field(input) {
+            Some((_, member)) => quote!(JsonFormsField::jsonforms_field_view(&self.#member)),
+            None => return Ok(quote!()),
+        },
+        syn::Data::Enum(e) => {
+            let container = attr::Container::from_attrs(&input.attrs).map_err(|err| vec![err])?;
+            if !matches!(container.tag, attr::TagType::External) {
+                return Ok(quote!());
+            }
+            let mut arms = TokenStream::new();
+            for v in &e.variants {
+                let serde = attr::Variant::from_ast(v, &container).map_err(|err| vec![err])?;
+                if serde.skip || !matches!(v.fields, syn::Fields::Unit) {
+                    continue;
+                }
+                let (variant, name) = (&v.ident, serde.name);
+                arms.extend(quote!(
+                    Self::#variant => JsonFormsValue::String(std::borrow::Cow::Borrowed(#name)),
+                ));
+            }
+            if arms.is_empty() {
+                return Ok(quote!());
+            }
+            quote!(
+                #[allow(unreachable_patterns)]
+                match self {
+                    #arms
+                    _ => JsonFormsValue::Other,
+                }
+            )
+        }
+        _ => return Ok(quote!()),
+    };
+    Ok(quote! {
+        fn jsonforms_view(&self) -> JsonFormsValue<'_> {
+            #view
+        }
+    })
+}
+
+/// the field of a newtype or of a serde transparent struct, those have the
+/// schema and the uischema of their field
+fn newtype_field(input: &DeriveInput) -> Option<(&syn::Field, syn::Member)> {
+    let syn::Data::Struct(s) = &input.data else {
+        return None;
+    };
+    let transparent = matches!(s.fields, syn::Fields::Named(_))
+        && attr::Container::from_attrs(&input.attrs).is_ok_and(|container| container.transparent);
+    if s.fields.len() != 1 || !(transparent || matches!(s.fields, syn::Fields::Unnamed(_))) {
+        return None;
+    }
+    let f = s.fields.iter().next()?;
+    let member = match &f.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(0.into()),
+    };
+    Some((f, member))
+}
+
+/// `checks` with the named `fields` of the struct bound by reference
+fn bind_fields(fields: &syn::Fields, checks: TokenStream) -> TokenStream {
+    if checks.is_empty() {
+        return checks;
+    }
+    let idents = fields.iter().filter_map(|f| f.ident.as_ref());
+    quote!(
+        let Self { #(#idents,)* .. } = self;
+        #checks
+    )
+}
+
+/// `head` are extra schema members written before `"type":"object"`,
+/// `tag` is the (name, value) of a serde internal tag property,
+/// `container` are the serde attributes applying to all fields, `defaults`
+/// adds the field values of `jsonforms_default_value` as schema defaults and
+/// `attrs` are the attributes of the struct or variant.
+/// Returns the schema, the uischema elements and the server side checks,
+/// which use the fields bound by name
 fn expand_struct(
-    s: &syn::DataStruct,
-    scope: String,
-) -> Result<(TokenStream, TokenStream), Vec<syn::Error>> {
-    let mut out_tokens: TokenStream = TokenStream::new();
-    let mut uiout_tokens: TokenStream = TokenStream::new();
-    let mut out = String::new();
-    let mut uiout = String::new();
+    fields: &syn::Fields,
+    head: &str,
+    tag: Option<(&str, &str)>,
+    container: &attr::Container,
+    defaults: bool,
+    attrs: &[syn::Attribute],
+) -> Result<(TokenStream, TokenStream, TokenStream), Vec<syn::Error>> {
+    let mut out = OutTokens::new("out_str");
+    let mut layouts = UiLayouts::default();
+    let mut end_layouts = Vec::<Span>::new();
     let mut sep = false;
-    let mut uisep = false;
-    let mut has_init_layout = false;
-    let mut required = Vec::<&syn::Ident>::new();
-    out += r#"{"type":"object","#;
-    out += r#""properties":{"#;
-    for f in &s.fields {
-        let mut skip = false;
-        let MyType(f_type, array_type, is_option) = get_type(&f.ty);
-        if f_type.is_none() {
-            continue;
+    let mut required = Vec::<String>::new();
+    let mut dependent_required = Vec::<String>::new();
+    let mut all_of = Vec::<String>::new();
+    let mut checks = TokenStream::new();
+    let mut flatten = Vec::<(&syn::Type, bool)>::new();
+    let mut errors = Vec::<syn::Error>::new();
+    if let Err(err) = parse_named_layouts(attrs, &mut layouts) {
+        errors.push(err);
+    }
+    out.push_str("{");
+    out.push_str(head);
+    out.push_str(r#""type":"object","#);
+    out.push_str(r#""properties":{"#);
+    if let Some((tag, value)) = tag {
+        out.push_str(&format!(
+            r#"{}:{{"const":{}}}"#,
+            json_str(tag),
+            json_str(value)
+        ));
+        required.push(tag.to_owned());
+        sep = true;
+    }
+    // rules and constraints refer to the other fields by their rust or serde name
+    let siblings: Vec<Sibling> = fields
+        .iter()
+        .filter_map(|f| {
+            let serde = attr::Field::from_ast(f, container).ok()?;
+            (!serde.skip).then_some(Sibling {
+                ident: f.ident.as_ref()?,
+                name: serde.name,
+                is_option: get_type(&f.ty).is_option,
+            })
+        })
+        .collect();
+    // struct level hooks run after the checks of the fields
+    let mut hooks = TokenStream::new();
+    if let Err(err) = parse_struct_checks(attrs, &siblings, &mut all_of, &mut checks, &mut hooks) {
+        errors.push(err);
+    }
+    for f in fields {
+        // EndLayout closes after the element of its field
+        for span in end_layouts.drain(..) {
+            errors.extend(layouts.close(span).err());
         }
+        let serde = attr::Field::from_ast(f, container).map_err(|err| vec![err])?;
+        let mut skip = serde.skip;
+        let f_type = get_type(&f.ty);
 
-        let mut end_layout = false;
-        let mut schemas: Vec<String> = Vec::new();
-        let mut uischemas: Vec<String> = Vec::new();
+        let mut open_layouts = Vec::<(LayoutKind, String, Span)>::new();
+        let mut schemas: Vec<(String, Span)> = Vec::new();
+        let mut uischemas: Vec<(String, Span)> = Vec::new();
+        let mut options: Vec<(String, Span)> = Vec::new();
+        let mut rule: Option<(&str, Condition)> = None;
+        let mut must_equal: Option<(&Sibling, Span)> = None;
+        let mut required_if: Option<(Condition, Span)> = None;
+        let mut dependents = Vec::<&Sibling>::new();
+        let mut field_hooks = Vec::<(syn::ExprPath, Span)>::new();
+        let mut array: Option<(String, Span)> = None;
+        let mut named_layout: Option<(String, Span)> = None;
+        let mut order = 0;
         let attrs = &f.attrs;
         for attr in attrs {
             if attr.path() != JSONFORMS {
@@ -124,132 +411,1750 @@ fn expand_struct(
                 if meta.path == SKIP {
                     skip = true;
                 } else if meta.path == SCHEMA {
-                    if let Some(lit) = get_lit_str(&meta).unwrap() {
-                        schemas.push(lit.value());
-                    }
+                    let lit = get_lit_str(&meta)?;
+                    schemas.push((lit.value(), lit.span()));
                 } else if meta.path == UISCHEMA {
-                    if let Some(lit) = get_lit_str(&meta).unwrap() {
-                        uischemas.push(lit.value());
-                    }
-                } else if meta.path == HLAYOUT {
-                    if uisep {
-                        uiout += ",";
+                    let lit = get_lit_str(&meta)?;
+                    uischemas.push((lit.value(), lit.span()));
+                } else if let Some((kind, head)) = parse_layout_marker(&meta)? {
+                    open_layouts.push((kind, head, meta.path.span()));
+                } else if meta.path == LAYOUT {
+                    let lit = get_lit_str(&meta)?;
+                    named_layout = Some((lit.value(), lit.span()));
+                } else if meta.path == ORDER {
+                    order = get_order(&meta)?;
+                } else if meta.path == ELAYOUT {
+                    end_layouts.push(meta.path.span());
+                } else if let Some((_, effect)) =
+                    RULE_EFFECTS.iter().find(|(symbol, _)| meta.path == *symbol)
+                {
+                    if rule.is_some() {
+                        return Err(meta.error("a field can only have one rule"));
                     }
-                    uiout += r#"{"type":"HorizontalLayout","elements":["#;
-                    uisep = false;
-                    has_init_layout = true;
-                } else if meta.path == VLAYOUT {
-                    if uisep {
-                        uiout += ",";
+                    rule = Some((effect, parse_condition(&meta, &siblings)?));
+                } else if meta.path == MUST_EQUAL {
+                    let lit = get_lit_str(&meta)?;
+                    let other = find_sibling(&siblings, &lit)?;
+                    // relative json pointer, needs the `$data` option of ajv
+                    let data = json_str(&format!("1/{}", json_pointer_token(&other.name)));
+                    schemas.push((format!(r#""const":{{"$data":{}}}"#, data), lit.span()));
+                    must_equal = Some((other, lit.span()));
+                } else if meta.path == REQUIRED_IF {
+                    required_if = Some((parse_condition(&meta, &siblings)?, meta.path.span()));
+                } else if meta.path == DEPENDENT_REQUIRED {
+                    dependents.push(find_sibling(&siblings, &get_lit_str(&meta)?)?);
+                } else if meta.path == VALIDATE_WITH {
+                    let lit = get_lit_str(&meta)?;
+                    field_hooks.push((lit.parse()?, lit.span()));
+                } else if meta.path == ARRAY {
+                    let lit = get_lit_str(&meta)?;
+                    if !["table", "list"].contains(&lit.value().as_str()) {
+                        return Err(syn::Error::new(lit.span(), r#"expected "table" or "list""#));
                     }
-                    uiout += r#"{"type":"VerticalLayout","elements":["#;
-                    uisep = false;
-                    has_init_layout = true;
-                } else if meta.path == ELAYOUT {
-                    end_layout = true;
+                    array = Some((lit.value(), lit.span()));
+                } else if !parse_field_attr(
+                    &meta,
+                    f_type.json_type,
+                    &mut schemas,
+                    &mut uischemas,
+                    &mut options,
+                )? {
+                    return Err(meta.error("unknown jsonforms field attribute"));
                 }
                 Ok(())
             }) {
-                return Err(vec![syn::Error::new(
-                    f.span(),
-                    format!(
-                        "expand_struct: invalid meta parse for jsonforms attribute {:?}",
-                        err
-                    ),
-                )]);
+                return Err(vec![err]);
             }
         }
+        // array items whose uischema is a layout are rendered with it, unless
+        // the array is a table. Known at runtime, see JSONFORMS_LAYOUT
+        let is_array = f_type.json_type == Some("array") && f_type.items.is_some();
+        let detail = match (&f_type.items, &array) {
+            (_, Some((kind, _))) if kind == "table" => None,
+            (Some(items), _) if is_array => items.ref_type,
+            _ => None,
+        };
+        let list = match &array {
+            Some((kind, span)) if kind == "list" && detail.is_none() => {
+                errors.push(syn::Error::new(
+                    *span,
+                    "`array = \"list\"` needs items rendered as a layout",
+                ));
+                false
+            }
+            Some((_, span)) if !is_array => {
+                errors.push(syn::Error::new(*span, "`array` needs an array field"));
+                false
+            }
+            Some((kind, _)) => kind == "list",
+            None => false,
+        };
+        let options = join(options.iter().map(|(option, _)| option), ",");
+        if detail.is_none() && !options.is_empty() {
+            uischemas.push((format!(r#""options":{{{}}}"#, options), f.span()));
+        }
+        if let (Some((_, span)), false) = (&named_layout, open_layouts.is_empty()) {
+            errors.push(syn::Error::new(
+                *span,
+                "a field placed in a named layout can't open layouts",
+            ));
+        }
+        for (kind, head, span) in open_layouts {
+            errors.extend(layouts.open(kind, head, order, span).err());
+        }
         if skip {
             continue;
         }
-        if let Some(id) = &f.ident {
-            if sep {
-                out += ",";
+        // members set by the derive itself can't be redefined by attributes
+        let (schema_keys, uischema_keys): (&[&str], &[&str]) = if f_type.ref_type.is_some() {
+            (REF_SCHEMA_KEYS, REF_UISCHEMA_KEYS)
+        } else if f_type.items.is_some() {
+            (&["type", f_type.item_key()], CONTROL_KEYS)
+        } else {
+            (&["type"], CONTROL_KEYS)
+        };
+        let mut uischema_keys = uischema_keys.to_vec();
+        if rule.is_some() {
+            uischema_keys.push("rule");
+        }
+        if detail.is_some() {
+            uischema_keys.push("options");
+        }
+        let mut field_errors = check_json_members(&schemas, schema_keys);
+        field_errors.extend(check_json_members(&uischemas, &uischema_keys));
+        if !field_errors.is_empty() {
+            errors.extend(field_errors);
+            continue;
+        }
+        // doc comments give the title and description unless set explicitly
+        let (doc_title, doc_description) = get_doc(&f.attrs);
+        for (key, doc) in [("title", doc_title), ("description", doc_description)] {
+            if let Some(doc) = doc {
+                if get_member(&schemas, key).is_none() {
+                    schemas.push((format!(r#""{}":{}"#, key, json_str(&doc)), f.span()));
+                }
+            }
+        }
+        // a Group has no schema, its label comes from the field title
+        if f_type.ref_type.is_some() && get_member(&uischemas, "label").is_none() {
+            if let Some(title) = get_member(&schemas, "title") {
+                uischemas.push((format!(r#""label":{}"#, title), f.span()));
+            }
+        }
+        let schemas: Vec<String> = schemas.into_iter().map(|(schema, _)| schema).collect();
+        let uischemas: Vec<String> = uischemas
+            .into_iter()
+            .map(|(uischema, _)| uischema)
+            .collect();
+        if let Some(ident) = &f.ident {
+            let name = serde.name;
+            let pointer = json_pointer_expr(&name);
+            if serde.flatten {
+                if let Some(ref_type) = f_type.ref_type {
+                    let mut flatten_check = quote!(
+                        <#ref_type as JsonFormsSerializable>::jsonforms_check(
+                            #ident,
+                            jsonforms_pointer,
+                            jsonforms_errors,
+                        );
+                    );
+                    if f_type.is_option {
+                        flatten_check = quote!(
+                            if let Some(#ident) = #ident {
+                                #flatten_check
+                            }
+                        );
+                    }
+                    checks.extend(flatten_check);
+                }
             } else {
-                sep = true;
-            }
-            out += &format!(r#""{}":"#, id);
-            out += r#"{"type":""#;
-            let f_type_str = f_type.unwrap();
-            out += f_type_str;
-            out += "\"";
-            for sch in schemas {
-                out += ",";
-                out += &sch;
-            }
-            if let Some(arr_type) = array_type {
-                out_tokens.extend(quote!(
-                    {
-                        out_str += #out;
-                        out_str += r#","items":"#;
-                        let (item_obj, _) = #arr_type::jsonforms_schema();
-                        out_str += &item_obj;
+                let constraints = get_constraints(&schemas);
+                let value_checks = value_checks(&f_type, ident, constraints.as_deref());
+                if !value_checks.is_empty() {
+                    checks.extend(quote!({
+                        let jsonforms_value_pointer = #pointer;
+                        #value_checks
+                    }));
+                }
+            }
+            // a flattened field has no pointer of its own
+            let hook_pointer = if serde.flatten {
+                quote!(jsonforms_pointer)
+            } else {
+                pointer.clone()
+            };
+            for (path, span) in field_hooks {
+                checks.extend(quote_spanned!(span=>
+                    if let Err(error) = #path(#ident, self) {
+                        jsonforms_errors.push(JsonFormsError::new(#hook_pointer, error.to_string()));
+                    }
+                ));
+            }
+            if let Some((other, span)) = must_equal {
+                let other_ident = other.ident;
+                let message = format!("must be equal to {}", other.name);
+                checks.extend(quote_spanned!(span=>
+                    if #ident != #other_ident {
+                        jsonforms_errors.push(JsonFormsError::new(#pointer, #message));
+                    }
+                ));
+            }
+            if let Some((condition, span)) = required_if {
+                if !f_type.is_option {
+                    errors.push(syn::Error::new(span, "required_if needs an Option field"));
+                } else {
+                    match condition.to_check() {
+                        Ok(check) => checks.extend(quote!(
+                            if #check && #ident.is_none() {
+                                jsonforms_errors.push(JsonFormsError::new(#pointer, "is required"));
+                            }
+                        )),
+                        Err(err) => errors.push(err),
+                    }
+                    all_of.push(format!(
+                        r#"{{"if":{},"then":{{"required":[{}]}}}}"#,
+                        condition.to_schema(),
+                        json_str(&name)
+                    ));
+                }
+            }
+            if !dependents.is_empty() {
+                dependent_required.push(format!(
+                    "{}:[{}]",
+                    json_str(&name),
+                    join(dependents.iter().map(|other| json_str(&other.name)), ",")
+                ));
+                let present = if f_type.is_option {
+                    quote!(#ident.is_some())
+                } else {
+                    quote!(true)
+                };
+                // dependents that are not an Option are always there
+                let message = format!("is required when {} is set", name);
+                let missing: Vec<TokenStream> = dependents
+                    .iter()
+                    .filter(|other| other.is_option)
+                    .map(|other| required_check(other, &message))
+                    .collect();
+                if !missing.is_empty() {
+                    checks.extend(quote!(
+                        if #present {
+                            #(#missing)*
+                        }
+                    ));
+                }
+            }
+            if serde.flatten {
+                match f_type.ref_type {
+                    Some(ref_type) => flatten.push((ref_type, f_type.is_option)),
+                    None => {
+                        return Err(vec![syn::Error::new(
+                            f.ty.span(),
+                            "flatten field type must implement JsonFormsSerializable",
+                        )]);
+                    }
+                }
+            } else {
+                if sep {
+                    out.push_str(",");
+                } else {
+                    sep = true;
+                }
+                out.push_str(&format!("{}:", json_str(&name)));
+                if defaults {
+                    let mut field_out = OutTokens::new("out_str");
+                    expand_type_schema(&mut field_out, &f_type, &schemas);
+                    let field_out = field_out.into_tokens();
+                    out.push_tokens(quote!(
+                        out_str += &jsonforms_default(
+                            {
+                                let mut out_str = String::new();
+                                #field_out
+                                out_str
+                            },
+                            &jsonforms_default_value.#ident,
+                        );
+                    ));
+                } else {
+                    expand_type_schema(&mut out, &f_type, &schemas);
+                }
+            }
+
+            let mut uiout = OutTokens::new("uiout_str");
+            if let Some(ref_type) = f_type.ref_type {
+                let extra = match &rule {
+                    Some((effect, condition)) => {
+                        let mut extra = OutTokens::new("uiout_str");
+                        for uisch in &uischemas {
+                            extra.push_str(uisch);
+                            extra.push_str(",");
+                        }
+                        render_rule(&mut extra, effect, condition);
+                        let extra = extra.into_tokens();
+                        quote!(&{
+                            let mut uiout_str = String::new();
+                            #extra
+                            uiout_str
+                        })
                     }
+                    None => {
+                        let extra = join(uischemas, ",");
+                        quote!(#extra)
+                    }
+                };
+                // flattened fields live in the same object, their Group keeps our scope
+                let ref_scope = if serde.flatten {
+                    quote!(scope)
+                } else {
+                    // scopes are written as is in the uischema json
+                    let token = scope_token(&name);
+                    quote!(&format!("{}/properties/{}", scope, #token))
+                };
+                uiout.push_tokens(quote!(
+                    uiout_str += &<#ref_type as JsonFormsField>::jsonforms_field_uischema(
+                        #ref_scope,
+                        #extra,
+                    );
                 ));
-                out.clear();
+            } else {
+                let control = if list { "ListWithDetail" } else { "Control" };
+                uiout.push_str(&format!(r#"{{"type":"{}","scope":""#, control));
+                uiout.push_tokens(quote!(uiout_str += scope;));
+                uiout.push_str(&format!(r#"/properties/{}""#, scope_token(&name)));
+                for uisch in uischemas {
+                    uiout.push_str(",");
+                    uiout.push_str(&uisch);
+                }
+                // the detail scopes are relative to the array item
+                // only layouts make a detail, other items keep the plain options
+                if let Some(item_type) = detail {
+                    let plain = if options.is_empty() {
+                        String::new()
+                    } else {
+                        format!(r#","options":{{{}}}"#, options)
+                    };
+                    let head = if options.is_empty() {
+                        r#","options":{"detail":"#.to_owned()
+                    } else {
+                        format!(r#","options":{{{},"detail":"#, options)
+                    };
+                    if let (true, Some((_, span))) = (list, &array) {
+                        uiout.push_tokens(quote_spanned!(*span=>
+                            const {
+                                assert!(
+                                    <#item_type as JsonFormsField>::JSONFORMS_LAYOUT,
+                                    "`array = \"list\"` needs items rendered as a layout",
+                                )
+                            };
+                        ));
+                    }
+                    uiout.push_tokens(quote!(
+                        if <#item_type as JsonFormsField>::JSONFORMS_LAYOUT {
+                            uiout_str += #head;
+                            uiout_str += &<#item_type as JsonFormsField>::jsonforms_field_uischema("#", "");
+                            uiout_str += "}";
+                        } else {
+                            uiout_str += #plain;
+                        }
+                    ));
+                }
+                if let Some((effect, condition)) = &rule {
+                    uiout.push_str(",");
+                    render_rule(&mut uiout, effect, condition);
+                }
+                uiout.push_str("}");
+            }
+            let span = f.ident.as_ref().map_or(f.span(), |ident| ident.span());
+            let element = uiout.into_tokens();
+            errors.extend(
+                match &named_layout {
+                    Some((name, name_span)) => layouts.push_named(name, element, order, *name_span),
+                    None => layouts.push_element(element, order, span),
+                }
+                .err(),
+            );
+            if !f_type.is_option && !serde.default && !serde.flatten {
+                required.push(name);
+            }
+        }
+    }
+    out.push_str("}");
+    if !required.is_empty() {
+        out.push_str(r#","required": ["#);
+        out.push_str(&join(required.iter().map(|id| json_str(id)), ","));
+        out.push_str("]");
+    }
+    if !dependent_required.is_empty() {
+        out.push_str(r#","dependentRequired":{"#);
+        out.push_str(&join(dependent_required, ","));
+        out.push_str("}");
+    }
+    if !all_of.is_empty() {
+        out.push_str(r#","allOf":["#);
+        out.push_str(&join(all_of, ","));
+        out.push_str("]");
+    }
+    if container.deny_unknown_fields {
+        out.push_str(r#","additionalProperties":false"#);
+    }
+    out.push_str("}");
+    for span in end_layouts {
+        errors.extend(layouts.close(span).err());
+    }
+    errors.extend(layouts.unclosed());
+    errors.extend(layouts.unused_named());
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let mut out_tokens = out.into_tokens();
+    if !flatten.is_empty() {
+        let (flatten, optional): (Vec<_>, Vec<_>) = flatten.into_iter().unzip();
+        out_tokens = quote!(
+            out_str += &{
+                let mut out_str = String::new();
+                #out_tokens
+                #(jsonforms_flatten(
+                    &mut out_str,
+                    &<#flatten as JsonFormsSerializable>::jsonforms_definition(defs),
+                    #optional,
+                );)*
+                out_str
+            };
+        );
+    }
+    checks.extend(hooks);
+    Ok((out_tokens, layouts.into_tokens(), checks))
+}
+
+/// newtype structs are transparent, the schema of their field with the
+/// members of its attributes. Other tuple structs are fixed length arrays.
+/// `fields` are named for a serde transparent struct.
+/// Returns the schema, the uischema and the server side checks
+fn expand_tuple(
+    fields: &syn::Fields,
+    attrs: &[syn::Attribute],
+) -> Result<(TokenStream, TokenStream, TokenStream), Vec<syn::Error>> {
+    let newtype = fields.len() == 1;
+    let mut out = OutTokens::new("out_str");
+    let mut checks = TokenStream::new();
+    let mut items = Vec::<Ident>::new();
+    let mut errors = Vec::<syn::Error>::new();
+    for (i, f) in fields.iter().enumerate() {
+        let f_type = get_type(&f.ty);
+        let item = Ident::new(&format!("jsonforms_item{}", i), Span::call_site());
+        items.push(item.clone());
+        let mut schemas = Vec::<(String, Span)>::new();
+        let mut hooks = Vec::<(syn::ExprPath, Span)>::new();
+        if let Err(err) = parse_tuple_field(f, &mut schemas, &mut hooks) {
+            errors.push(err);
+            continue;
+        }
+        let schema_keys: &[&str] = if f_type.ref_type.is_some() {
+            REF_SCHEMA_KEYS
+        } else if f_type.items.is_some() {
+            &["type", f_type.item_key()]
+        } else {
+            &["type"]
+        };
+        let field_errors = check_json_members(&schemas, schema_keys);
+        if !field_errors.is_empty() {
+            errors.extend(field_errors);
+            continue;
+        }
+        // a newtype takes the doc comments of the struct
+        let (mut doc_title, mut doc_description) = get_doc(&f.attrs);
+        if newtype {
+            let meta = FormMeta::from_attrs(attrs).map_err(|err| vec![err])?;
+            doc_title = doc_title.or(meta.title);
+            doc_description = doc_description.or(meta.description);
+        }
+        for (key, doc) in [("title", doc_title), ("description", doc_description)] {
+            if let Some(doc) = doc {
+                if get_member(&schemas, key).is_none() {
+                    schemas.push((format!(r#""{}":{}"#, key, json_str(&doc)), f.span()));
+                }
             }
-            out += "}";
+        }
+        let schemas: Vec<String> = schemas.into_iter().map(|(schema, _)| schema).collect();
+        if i > 0 {
+            out.push_str(",");
+        }
+        expand_type_schema(&mut out, &f_type, &schemas);
 
-            if uisep {
-                uiout += ",";
+        let constraints = get_constraints(&schemas);
+        let mut item_checks = value_checks(&f_type, &item, constraints.as_deref());
+        for (path, span) in hooks {
+            item_checks.extend(quote_spanned!(span=>
+                if let Err(error) = #path(#item, self) {
+                    jsonforms_errors.push(JsonFormsError::new(
+                        jsonforms_value_pointer.clone(),
+                        error.to_string(),
+                    ));
+                }
+            ));
+        }
+        if !item_checks.is_empty() {
+            let pointer = if newtype {
+                quote!(jsonforms_pointer.to_owned())
             } else {
-                if !has_init_layout {
-                    //default Horizontal Layout if MyType::default() specified
-                    uiout += r#"{"type":"VerticalLayout","elements":["#;
-                    has_init_layout = true;
+                json_pointer_expr(&i.to_string())
+            };
+            checks.extend(quote!({
+                let jsonforms_value_pointer = #pointer;
+                #item_checks
+            }));
+        }
+    }
+    if !newtype {
+        let meta = FormMeta::from_attrs(attrs).map_err(|err| vec![err])?;
+        let item_tokens = std::mem::replace(&mut out, OutTokens::new("out_str")).into_tokens();
+        out.push_tokens(quote!(
+            let jsonforms_items = {
+                let mut out_str = String::new();
+                #item_tokens
+                out_str
+            };
+        ));
+        out.push_str("{");
+        out.push_str(&meta.head());
+        // `prefixItems` is draft 2020-12, the draft-07 ajv of the client reads `items`
+        out.push_str(r#""type":"array","prefixItems":["#);
+        out.push_tokens(quote!(out_str += &jsonforms_items;));
+        out.push_str(r#"],"items":["#);
+        out.push_tokens(quote!(out_str += &jsonforms_items;));
+        let len = fields.len();
+        out.push_str(&format!(r#"],"minItems":{},"maxItems":{}}}"#, len, len));
+    }
+    // struct level hooks, conditionals have no field to refer to
+    let mut hooks = TokenStream::new();
+    if let Err(err) = parse_struct_checks(attrs, &[], &mut Vec::new(), &mut checks, &mut hooks) {
+        errors.push(err);
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    checks.extend(hooks);
+    if !checks.is_empty() {
+        let members = fields.members();
+        checks = quote!(
+            let Self { #(#members: #items),* } = self;
+            #checks
+        );
+    }
+    let ref_type = fields
+        .iter()
+        .next()
+        .filter(|_| newtype)
+        .and_then(|f| get_type(&f.ty).ref_type);
+    let uiout = match ref_type {
+        Some(ref_type) => quote!(
+            uiout_str += &<#ref_type as JsonFormsField>::jsonforms_field_uischema(scope, "");
+        ),
+        None => quote!(uiout_str += &jsonforms_control(scope, "");),
+    };
+    Ok((out.into_tokens(), uiout, checks))
+}
+
+/// schema members and validate_with hooks of a tuple struct field, the
+/// uischema is set on the fields using the struct
+fn parse_tuple_field(
+    f: &syn::Field,
+    schemas: &mut Vec<(String, Span)>,
+    hooks: &mut Vec<(syn::ExprPath, Span)>,
+) -> syn::Result<()> {
+    for attr in &f.attrs {
+        if attr.path() != JSONFORMS {
+            continue;
+        }
+
+        if let syn::Meta::List(meta) = &attr.meta {
+            if meta.tokens.is_empty() {
+                continue;
+            }
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path == SCHEMA {
+                let lit = get_lit_str(&meta)?;
+                schemas.push((lit.value(), lit.span()));
+            } else if meta.path == VALIDATE_WITH {
+                let lit = get_lit_str(&meta)?;
+                hooks.push((lit.parse()?, lit.span()));
+            } else {
+                let mut uischemas = Vec::new();
+                let mut options = Vec::new();
+                let json_type = get_type(&f.ty).json_type;
+                if !parse_field_attr(&meta, json_type, schemas, &mut uischemas, &mut options)? {
+                    return Err(meta.error("unknown jsonforms tuple field attribute"));
+                }
+                if !uischemas.is_empty() || !options.is_empty() {
+                    return Err(
+                        meta.error("uischema attributes go on the fields using the tuple struct")
+                    );
                 }
-                uisep = true;
             }
-            uiout += &format!(r#"{{"type":"Control","scope":"{}/{}""#, &scope, id);
-            for uisch in uischemas {
-                uiout += ",";
-                uiout += &uisch;
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+/// unit-only enums become a string `enum`, enums with data become `oneOf`
+/// one sub-schema per variant, following the serde enum representation
+fn expand_enum(
+    e: &syn::DataEnum,
+    attrs: &[syn::Attribute],
+) -> Result<(TokenStream, TokenStream, TokenStream), Vec<syn::Error>> {
+    let container = attr::Container::from_attrs(attrs).map_err(|err| vec![err])?;
+    let mut variants = Vec::new();
+    for v in &e.variants {
+        let serde = attr::Variant::from_ast(v, &container).map_err(|err| vec![err])?;
+        if serde.skip || has_skip(&v.attrs)? {
+            continue;
+        }
+        variants.push((v, serde));
+    }
+
+    let mut out = OutTokens::new("out_str");
+    let mut arms = TokenStream::new();
+    let head = FormMeta::from_attrs(attrs).map_err(|err| vec![err])?.head();
+    let unit_only = variants
+        .iter()
+        .all(|(v, _)| matches!(v.fields, syn::Fields::Unit));
+    if unit_only && matches!(container.tag, attr::TagType::External) {
+        out.push_str("{");
+        out.push_str(&head);
+        out.push_str(r#""type":"string","enum":["#);
+        out.push_str(&join(
+            variants.iter().map(|(_, serde)| json_str(&serde.name)),
+            ",",
+        ));
+        out.push_str("]}");
+    } else {
+        out.push_str("{");
+        out.push_str(&head);
+        out.push_str(r#""oneOf":["#);
+        for (i, (v, serde)) in variants.iter().enumerate() {
+            if i > 0 {
+                out.push_str(",");
             }
-            uiout += "}";
-            if end_layout {
-                uiout += "]}";
-                uisep = true;
+            arms.extend(expand_variant(&mut out, v, serde, &container.tag)?);
+        }
+        out.push_str("]");
+        if let attr::TagType::Internal { tag } | attr::TagType::Adjacent { tag, .. } =
+            &container.tag
+        {
+            out.push_str(&format!(
+                r#","discriminator":{{"propertyName":{}}}"#,
+                json_str(tag)
+            ));
+        }
+        out.push_str("}");
+    }
+
+    let mut uiout = OutTokens::new("uiout_str");
+    uiout.push_str(r#"{"type":"VerticalLayout","elements":[{"type":"Control","scope":""#);
+    uiout.push_tokens(quote!(uiout_str += scope;));
+    uiout.push_str(r#""}]}"#);
+    Ok((out.into_tokens(), uiout.into_tokens(), arms))
+}
+
+/// appends the variant schema, returns the match arm of its server side checks
+fn expand_variant(
+    out: &mut OutTokens,
+    v: &syn::Variant,
+    serde: &attr::Variant,
+    tag: &attr::TagType,
+) -> Result<TokenStream, Vec<syn::Error>> {
+    let mut checks = TokenStream::new();
+    // where the variant fields are in the data, below the enum
+    let mut pointer = String::new();
+    let name = serde.name.as_str();
+    let container = attr::Container::from_variant(serde);
+    let name_str = json_str(name);
+    let title = match get_doc(&v.attrs) {
+        (Some(title), Some(description)) => format!(
+            r#""title":{},"description":{}"#,
+            json_str(&title),
+            json_str(&description)
+        ),
+        (Some(title), None) => format!(r#""title":{}"#, json_str(&title)),
+        _ => format!(r#""title":{}"#, name_str),
+    };
+    match (tag, &v.fields) {
+        (attr::TagType::External, syn::Fields::Unit) => {
+            out.push_str(&format!(r#"{{{},"const":{}}}"#, title, name_str));
+        }
+        (attr::TagType::External, fields) => {
+            out.push_str(&format!(
+                r#"{{{},"type":"object","properties":{{{}:"#,
+                title, name_str
+            ));
+            checks = expand_variant_data(out, v, fields, &container)?;
+            pointer = format!("/{}", json_pointer_token(name));
+            out.push_str(&format!(
+                r#"}},"required":[{}],"additionalProperties":false}}"#,
+                name_str
+            ));
+        }
+        (attr::TagType::Internal { tag }, syn::Fields::Unit) => {
+            out.push_str(&format!(
+                r#"{{{},"type":"object","properties":{{{}:{{"const":{}}}}},"required":[{}]}}"#,
+                title,
+                json_str(tag),
+                name_str,
+                json_str(tag)
+            ));
+        }
+        (attr::TagType::Internal { tag }, fields @ syn::Fields::Named(_)) => {
+            let (props, _, variant_checks) = expand_struct(
+                fields,
+                &format!("{},", title),
+                Some((tag, name)),
+                &container,
+                false,
+                &v.attrs,
+            )?;
+            out.push_tokens(props);
+            checks = variant_checks;
+        }
+        (attr::TagType::Internal { tag }, syn::Fields::Unnamed(fields)) => {
+            // serde puts the tag next to the fields of the inner object
+            let f_type = get_newtype(v, fields)?;
+            let mut inner = OutTokens::new("out_str");
+            match f_type.ref_type {
+                Some(ref_type) => inner.push_tokens(quote_spanned!(ref_type.span()=>
+                    out_str += &<#ref_type as JsonFormsSerializable>::jsonforms_definition(defs);
+                )),
+                None if f_type.json_type == Some("object") => {
+                    expand_type_schema(&mut inner, &f_type, &[])
+                }
+                None => {
+                    return Err(vec![syn::Error::new(
+                        fields.span(),
+                        "internally tagged newtype variants need an object type",
+                    )]);
+                }
             }
-            if !is_option {
-                required.push(id);
+            let inner = inner.into_tokens();
+            let head = format!(
+                r#"{{{},"type":"object","properties":{{{}:{{"const":{}}}}},"required":[{}]}}"#,
+                title,
+                json_str(tag),
+                name_str,
+                json_str(tag)
+            );
+            out.push_tokens(quote!(
+                out_str += &{
+                    let mut out_str = String::from(#head);
+                    let inner = {
+                        let mut out_str = String::new();
+                        #inner
+                        out_str
+                    };
+                    jsonforms_tagged(&mut out_str, &inner);
+                    out_str
+                };
+            ));
+            checks = newtype_checks(&f_type);
+        }
+        (attr::TagType::Adjacent { tag, content }, fields) => {
+            let tag = json_str(tag);
+            out.push_str(&format!(
+                r#"{{{},"type":"object","properties":{{{}:{{"const":{}}}"#,
+                title, tag, name_str
+            ));
+            if matches!(fields, syn::Fields::Unit) {
+                out.push_str(&format!(r#"}},"required":[{}]}}"#, tag));
+            } else {
+                pointer = format!("/{}", json_pointer_token(content));
+                let content = json_str(content);
+                out.push_str(&format!(r#",{}:"#, content));
+                checks = expand_variant_data(out, v, fields, &container)?;
+                out.push_str(&format!(r#"}},"required":[{},{}]}}"#, tag, content));
             }
         }
+        (attr::TagType::None, syn::Fields::Unit) => {
+            out.push_str(&format!(r#"{{{},"type":"null"}}"#, title));
+        }
+        (attr::TagType::None, syn::Fields::Named(_)) => {
+            let (props, _, variant_checks) = expand_struct(
+                &v.fields,
+                &format!("{},", title),
+                None,
+                &container,
+                false,
+                &v.attrs,
+            )?;
+            out.push_tokens(props);
+            checks = variant_checks;
+        }
+        (attr::TagType::None, syn::Fields::Unnamed(fields)) => {
+            let f_type = get_newtype(v, fields)?;
+            expand_type_schema(out, &f_type, &[title]);
+            checks = newtype_checks(&f_type);
+        }
     }
-    out += "}";
-    if !required.is_empty() {
-        out += r#","required": ["#;
-        out += &join(required.iter().map(|id| format!(r#""{}""#, id)), ",");
-        out += "]";
+    if checks.is_empty() {
+        return Ok(checks);
+    }
+    let variant = &v.ident;
+    let bindings = match &v.fields {
+        syn::Fields::Unnamed(_) => quote!((jsonforms_item)),
+        fields => {
+            let idents = fields.iter().filter_map(|f| f.ident.as_ref());
+            quote!({ #(#idents,)* .. })
+        }
+    };
+    Ok(quote!(
+        Self::#variant #bindings => {
+            let jsonforms_pointer = &format!("{}{}", jsonforms_pointer, #pointer);
+            #checks
+        }
+    ))
+}
+
+/// schema of the data carried by a struct or newtype variant, returns the
+/// server side checks of its fields
+fn expand_variant_data(
+    out: &mut OutTokens,
+    v: &syn::Variant,
+    fields: &syn::Fields,
+    container: &attr::Container,
+) -> Result<TokenStream, Vec<syn::Error>> {
+    match fields {
+        syn::Fields::Named(_) => {
+            let (props, _, checks) = expand_struct(fields, "", None, container, false, &v.attrs)?;
+            out.push_tokens(props);
+            return Ok(checks);
+        }
+        syn::Fields::Unnamed(fields) => {
+            let f_type = get_newtype(v, fields)?;
+            expand_type_schema(out, &f_type, &[]);
+            return Ok(newtype_checks(&f_type));
+        }
+        syn::Fields::Unit => (),
+    }
+    Ok(TokenStream::new())
+}
+
+/// checks of the value of a newtype variant, bound to `jsonforms_item`
+fn newtype_checks(f_type: &MyType) -> TokenStream {
+    let item = Ident::new("jsonforms_item", Span::call_site());
+    let checks = value_checks(f_type, &item, None);
+    if checks.is_empty() {
+        return checks;
+    }
+    quote!(
+        let jsonforms_value_pointer = jsonforms_pointer.to_owned();
+        #checks
+    )
+}
+
+fn get_newtype<'a>(
+    v: &syn::Variant,
+    fields: &'a syn::FieldsUnnamed,
+) -> Result<MyType<'a>, Vec<syn::Error>> {
+    if fields.unnamed.len() != 1 {
+        return Err(vec![syn::Error::new(
+            v.span(),
+            "tuple variants with more than one field not supported",
+        )]);
+    }
+    Ok(get_type(&fields.unnamed[0].ty))
+}
+
+/// appends the schema of a field type, `schemas` are extra raw schema members
+fn expand_type_schema(out: &mut OutTokens, f_type: &MyType, schemas: &[String]) {
+    if let Some(ref_type) = f_type.ref_type {
+        if schemas.is_empty() {
+            out.push_tokens(quote_spanned!(ref_type.span()=>
+                out_str += &<#ref_type as JsonFormsField>::jsonforms_field_schema(defs);
+            ));
+        } else {
+            let members = join(schemas, ",");
+            out.push_tokens(quote_spanned!(ref_type.span()=>
+                jsonforms_extend(
+                    &mut out_str,
+                    &<#ref_type as JsonFormsField>::jsonforms_field_schema(defs),
+                    #members,
+                );
+            ));
+        }
+        return;
+    }
+    out.push_str(r#"{"type":""#);
+    out.push_str(f_type.json_type.unwrap_or_default());
+    out.push_str("\"");
+    for sch in schemas {
+        out.push_str(",");
+        out.push_str(sch);
+    }
+    // the integer type range unless narrowed by attributes, so the client
+    // can't submit a value serde would reject
+    if let Some(range) = &f_type.range {
+        let prim = Ident::new(range.prim, Span::call_site());
+        if !has_member(schemas, "minimum") {
+            if range.non_zero && range.prim.starts_with('u') {
+                out.push_str(r#","minimum":1"#);
+            } else {
+                out.push_str(r#","minimum":"#);
+                out.push_tokens(quote!(out_str += &#prim::MIN.to_string();));
+            }
+        }
+        if !has_member(schemas, "maximum") {
+            out.push_str(r#","maximum":"#);
+            out.push_tokens(quote!(out_str += &#prim::MAX.to_string();));
+        }
+        if range.non_zero && range.prim.starts_with('i') {
+            out.push_str(r#","not":{"const":0}"#);
+        }
+    }
+    if f_type.unique_items && !has_member(schemas, "uniqueItems") {
+        out.push_str(r#","uniqueItems":true"#);
+    }
+    if let Some(items) = &f_type.items {
+        out.push_str(&format!(r#",{}:"#, json_str(f_type.item_key())));
+        expand_type_schema(out, items, &[]);
+    }
+    out.push_str("}");
+}
+
+const REF_SCHEMA_KEYS: &[&str] = &[
+    "type",
+    "properties",
+    "required",
+    "items",
+    "enum",
+    "oneOf",
+    "discriminator",
+];
+const REF_UISCHEMA_KEYS: &[&str] = &["type", "scope", "elements"];
+const CONTROL_KEYS: &[&str] = &["type", "scope"];
+
+/// every `members` fragment must parse as the members of a json object,
+/// without redefining a `reserved` key or a key from a previous fragment
+fn check_json_members(members: &[(String, Span)], reserved: &[&str]) -> Vec<syn::Error> {
+    let mut errors = Vec::new();
+    let mut keys: Vec<String> = reserved.iter().map(|key| key.to_string()).collect();
+    for (member, span) in members {
+        match serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&format!(
+            "{{{}}}",
+            member
+        )) {
+            Ok(map) => {
+                if let Some(serde_json::Value::String(pattern)) = map.get("pattern") {
+                    if let Err(err) = check_pattern(pattern, *span) {
+                        errors.push(err);
+                    }
+                }
+                if let Some(serde_json::Value::String(format)) = map.get("format") {
+                    if let Err(err) = check_format(format, *span) {
+                        errors.push(err);
+                    }
+                }
+                for key in map.keys() {
+                    if keys.contains(key) {
+                        errors.push(syn::Error::new(
+                            *span,
+                            format!("`{}` is already set for this field", key),
+                        ));
+                    } else {
+                        keys.push(key.clone());
+                    }
+                }
+            }
+            Err(err) => errors.push(syn::Error::new(
+                *span,
+                format!("invalid json fragment `{}`, {}", member, err),
+            )),
+        }
+    }
+    errors
+}
+
+/// the server checks patterns with the regex crate, a pattern it can't
+/// compile (e.g. a lookahead) would never be enforced
+fn check_pattern(pattern: &str, span: Span) -> syn::Result<()> {
+    regex::Regex::new(pattern).map(|_| ()).map_err(|err| {
+        syn::Error::new(
+            span,
+            format!("pattern not supported by the server checks, {}", err),
+        )
+    })
+}
+
+/// formats of ajv-formats the server checks, another format would only be
+/// enforced by the client
+const CHECKED_FORMATS: &[&str] = &[
+    "date",
+    "time",
+    "date-time",
+    "email",
+    "uri",
+    "uri-reference",
+    "uuid",
+    "ipv4",
+    "ipv6",
+    "hostname",
+    "password",
+];
+
+fn check_format(format: &str, span: Span) -> syn::Result<()> {
+    if CHECKED_FORMATS.contains(&format) {
+        return Ok(());
+    }
+    Err(syn::Error::new(
+        span,
+        format!(
+            "format `{}` not supported by the server checks, expected one of {}",
+            format,
+            CHECKED_FORMATS.join(", ")
+        ),
+    ))
+}
+
+/// value of `key` in the json object `members`, invalid fragments are ignored
+fn get_member(members: &[(String, Span)], key: &str) -> Option<serde_json::Value> {
+    members.iter().rev().find_map(|(member, _)| {
+        serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&format!(
+            "{{{}}}",
+            member
+        ))
+        .ok()?
+        .remove(key)
+    })
+}
+
+/// true if one of the json object `members` has `key`
+fn has_member(members: &[String], key: &str) -> bool {
+    members.iter().any(|member| {
+        serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&format!(
+            "{{{}}}",
+            member
+        ))
+        .is_ok_and(|map| map.contains_key(key))
+    })
+}
+
+/// the first doc comment line is the title, the next ones the description
+fn get_doc(attrs: &[syn::Attribute]) -> (Option<String>, Option<String>) {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        if !attr.path().is_ident("doc") {
+            continue;
+        }
+        if let syn::Meta::NameValue(syn::MetaNameValue {
+            value:
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit),
+                    ..
+                }),
+            ..
+        }) = &attr.meta
+        {
+            lines.extend(lit.value().split('\n').map(|line| line.trim().to_owned()));
+        }
+    }
+    let mut lines = lines.into_iter().skip_while(|line| line.is_empty());
+    let title = lines.next();
+    let mut description = String::new();
+    for line in lines {
+        if line.is_empty() {
+            if !description.is_empty() && !description.ends_with('\n') {
+                description.push('\n');
+            }
+        } else {
+            if !description.is_empty() && !description.ends_with('\n') {
+                description.push(' ');
+            }
+            description += &line;
+        }
+    }
+    let description = description.trim_end().to_owned();
+    (title, Some(description).filter(|d| !d.is_empty()))
+}
+
+/// struct level jsonforms attributes, they describe the whole form
+#[derive(Default)]
+struct FormMeta {
+    debug: bool,
+    title: Option<String>,
+    description: Option<String>,
+    id: Option<String>,
+    /// the span of the attribute, for the errors of tuple structs and enums
+    deny_unknown_fields: Option<Span>,
+    default: Option<Span>,
+}
+
+impl FormMeta {
+    /// title and description default to the doc comments
+    fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let (title, description) = get_doc(attrs);
+        let mut form = Self {
+            title,
+            description,
+            ..Self::default()
+        };
+        for attr in attrs {
+            if attr.path() != JSONFORMS {
+                continue;
+            }
+
+            if let syn::Meta::List(meta) = &attr.meta {
+                if meta.tokens.is_empty() {
+                    continue;
+                }
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path == DEBUG {
+                    form.debug = true;
+                } else if meta.path == TITLE {
+                    form.title = Some(get_lit_str(&meta)?.value());
+                } else if meta.path == DESCRIPTION {
+                    form.description = Some(get_lit_str(&meta)?.value());
+                } else if meta.path == ID {
+                    form.id = Some(get_lit_str(&meta)?.value());
+                } else if meta.path == DENY_UNKNOWN_FIELDS {
+                    form.deny_unknown_fields = Some(meta.path.span());
+                } else if meta.path == DEFAULT {
+                    form.default = Some(meta.path.span());
+                } else {
+                    attr::skip_meta(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(form)
+    }
+
+    /// title and description schema members, written before the type
+    fn head(&self) -> String {
+        let mut head = String::new();
+        if let Some(title) = &self.title {
+            head += &format!(r#""title":{},"#, json_str(title));
+        }
+        if let Some(description) = &self.description {
+            head += &format!(r#""description":{},"#, json_str(description));
+        }
+        head
+    }
+}
+
+/// additionalProperties and field defaults only make sense for a struct
+/// with named fields
+fn needs_named_fields(attrs: &[syn::Attribute]) -> Result<(), Vec<syn::Error>> {
+    let meta = FormMeta::from_attrs(attrs).map_err(|err| vec![err])?;
+    for (span, name) in [
+        (meta.deny_unknown_fields, "deny_unknown_fields"),
+        (meta.default, "default"),
+    ] {
+        if let Some(span) = span {
+            return Err(vec![syn::Error::new(
+                span,
+                format!("{} needs a struct with named fields", name),
+            )]);
+        }
+    }
+    Ok(())
+}
+
+/// typed field attributes, mapped to a schema or uischema member
+static SCHEMA_STR_ATTRS: &[(Symbol, &str)] = &[(TITLE, "title"), (DESCRIPTION, "description")];
+static SCHEMA_UINT_ATTRS: &[(Symbol, &str)] = &[
+    (MIN_LENGTH, "minLength"),
+    (MAX_LENGTH, "maxLength"),
+    (MIN_ITEMS, "minItems"),
+    (MAX_ITEMS, "maxItems"),
+];
+static SCHEMA_NUMBER_ATTRS: &[(Symbol, &str)] = &[(MINIMUM, "minimum"), (MAXIMUM, "maximum")];
+static UISCHEMA_STR_ATTRS: &[(Symbol, &str)] = &[(LABEL, "label")];
+static OPTIONS_BOOL_ATTRS: &[(Symbol, &str)] = &[
+    (MULTI, "multi"),
+    (READONLY, "readonly"),
+    (SHOW_SORT_BUTTONS, "showSortButtons"),
+];
+static OPTIONS_STR_ATTRS: &[(Symbol, &str)] = &[(ELEMENT_LABEL_PROP, "elementLabelProp")];
+
+/// schema keywords the server checks on a field value
+const CHECKED_KEYWORDS: &[&str] = &[
+    "minLength",
+    "maxLength",
+    "pattern",
+    "format",
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "multipleOf",
+    "minItems",
+    "maxItems",
+    "uniqueItems",
+    "enum",
+    "const",
+];
+
+/// json object of the checked keywords among the field schema members,
+/// `$data` references are checked by the cross-field constraints
+fn get_constraints(schemas: &[String]) -> Option<String> {
+    let mut constraints = serde_json::Map::new();
+    for member in schemas {
+        let Ok(map) = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&format!(
+            "{{{}}}",
+            member
+        )) else {
+            continue;
+        };
+        for (key, value) in map {
+            if CHECKED_KEYWORDS.contains(&key.as_str()) && value.get("$data").is_none() {
+                constraints.insert(key, value);
+            }
+        }
+    }
+    (!constraints.is_empty()).then(|| serde_json::Value::Object(constraints).to_string())
+}
+
+/// server side checks of the value of type `f_type` bound to `value`, found at
+/// the runtime `jsonforms_value_pointer`. `constraints` are checked on the
+/// value, nested values are checked by their JsonFormsField implementation
+fn value_checks(f_type: &MyType, value: &Ident, constraints: Option<&str>) -> TokenStream {
+    let mut checks = TokenStream::new();
+    if let Some(schema) = constraints {
+        checks.extend(quote!(
+            jsonforms_check_value(#value, #schema, &jsonforms_value_pointer, jsonforms_errors);
+        ));
+    }
+    if let Some(items) = &f_type.items {
+        let item = Ident::new("jsonforms_item", Span::call_site());
+        let item_checks = value_checks(items, &item, None);
+        if !item_checks.is_empty() && f_type.json_type == Some("object") {
+            checks.extend(quote!(
+                for (jsonforms_key_value, #item) in #value.iter() {
+                    let jsonforms_value_pointer = format!(
+                        "{}/{}",
+                        jsonforms_value_pointer,
+                        jsonforms_key(jsonforms_key_value)
+                    );
+                    #item_checks
+                }
+            ));
+        } else if !item_checks.is_empty() {
+            checks.extend(quote!(
+                for (jsonforms_index, #item) in #value.iter().enumerate() {
+                    let jsonforms_value_pointer =
+                        format!("{}/{}", jsonforms_value_pointer, jsonforms_index);
+                    #item_checks
+                }
+            ));
+        }
+    } else if let Some(ref_type) = f_type.ref_type {
+        checks.extend(quote_spanned!(ref_type.span()=>
+            <#ref_type as JsonFormsField>::jsonforms_field_check(
+                #value,
+                &jsonforms_value_pointer,
+                jsonforms_errors,
+            );
+        ));
+    }
+    if f_type.is_option && !checks.is_empty() {
+        checks = quote!(
+            if let Some(#value) = #value {
+                #checks
+            }
+        );
+    }
+    checks
+}
+
+/// returns false if `meta` is not a typed field attribute
+fn parse_field_attr(
+    meta: &ParseNestedMeta,
+    json_type: Option<&str>,
+    schemas: &mut Vec<(String, Span)>,
+    uischemas: &mut Vec<(String, Span)>,
+    options: &mut Vec<(String, Span)>,
+) -> syn::Result<bool> {
+    let span = meta.path.span();
+    let find = |attrs: &[(Symbol, &'static str)]| {
+        attrs
+            .iter()
+            .find(|(symbol, _)| meta.path == *symbol)
+            .map(|(_, key)| *key)
+    };
+    check_field_type(meta, json_type)?;
+    if meta.path == PATTERN {
+        let lit = get_lit_str(meta)?;
+        check_pattern(&lit.value(), lit.span())?;
+        schemas.push((format!(r#""pattern":{}"#, json_str(&lit.value())), span));
+    } else if meta.path == FORMAT {
+        let lit = get_lit_str(meta)?;
+        check_format(&lit.value(), lit.span())?;
+        schemas.push((format!(r#""format":{}"#, json_str(&lit.value())), span));
+    } else if let Some(key) = find(SCHEMA_STR_ATTRS) {
+        schemas.push((format!(r#""{}":{}"#, key, get_json_str(meta)?), span));
+    } else if let Some(key) = find(SCHEMA_UINT_ATTRS) {
+        schemas.push((format!(r#""{}":{}"#, key, get_json_uint(meta)?), span));
+    } else if let Some(key) = find(SCHEMA_NUMBER_ATTRS) {
+        schemas.push((format!(r#""{}":{}"#, key, get_json_number(meta)?), span));
+    } else if meta.path == DEFAULT {
+        let default = get_json_literal(meta)?;
+        check_default_type(&default, json_type, span)?;
+        schemas.push((format!(r#""default":{}"#, default), span));
+    } else if let Some(key) = find(UISCHEMA_STR_ATTRS) {
+        uischemas.push((format!(r#""{}":{}"#, key, get_json_str(meta)?), span));
+    } else if let Some(key) = find(OPTIONS_BOOL_ATTRS) {
+        options.push((format!(r#""{}":{}"#, key, get_bool(meta)?), span));
+    } else if let Some(key) = find(OPTIONS_STR_ATTRS) {
+        options.push((format!(r#""{}":{}"#, key, get_json_str(meta)?), span));
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// the json types the typed schema attributes apply to
+static TYPED_ATTRS: &[(Symbol, &[&str])] = &[
+    (MIN_LENGTH, &["string"]),
+    (MAX_LENGTH, &["string"]),
+    (PATTERN, &["string"]),
+    (FORMAT, &["string"]),
+    (MINIMUM, &["integer", "number"]),
+    (MAXIMUM, &["integer", "number"]),
+    (MIN_ITEMS, &["array"]),
+    (MAX_ITEMS, &["array"]),
+];
+
+/// rejects a typed schema attribute on a field of another known json type
+fn check_field_type(meta: &ParseNestedMeta, json_type: Option<&str>) -> syn::Result<()> {
+    let Some(json_type) = json_type else {
+        return Ok(());
+    };
+    match TYPED_ATTRS.iter().find(|(symbol, _)| meta.path == *symbol) {
+        Some((symbol, types)) if !types.contains(&json_type) => Err(syn::Error::new(
+            meta.path.span(),
+            format!(
+                "`{}` does not apply to a field of type {}",
+                symbol, json_type
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// rejects a `default` literal serde would not read as a field of the known
+/// json type, integers are numbers too
+fn check_default_type(default: &str, json_type: Option<&str>, span: Span) -> syn::Result<()> {
+    let (Some(json_type), Ok(value)) = (
+        json_type,
+        serde_json::from_str::<serde_json::Value>(default),
+    ) else {
+        return Ok(());
+    };
+    let default_type = match value {
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(n) if n.is_f64() => "number",
+        _ => "integer",
+    };
+    if default_type == json_type || (default_type == "integer" && json_type == "number") {
+        Ok(())
+    } else {
+        Err(syn::Error::new(
+            span,
+            format!(
+                "a `{}` of type {} does not apply to a field of type {}",
+                DEFAULT, default_type, json_type
+            ),
+        ))
+    }
+}
+
+static RULE_EFFECTS: &[(Symbol, &str)] = &[
+    (HIDE_IF, "HIDE"),
+    (SHOW_IF, "SHOW"),
+    (ENABLE_IF, "ENABLE"),
+    (DISABLE_IF, "DISABLE"),
+];
+
+/// struct level `conditional(if(..), then = "{..}", else = "{..}")`, added to
+/// `allOf`, the server checks the `required` lists of `then` and `else`.
+/// `validate_with = "path"` hooks get the whole value, the enum of a variant,
+/// and return the errors with pointers relative to it
+fn parse_struct_checks(
+    attrs: &[syn::Attribute],
+    siblings: &[Sibling],
+    all_of: &mut Vec<String>,
+    checks: &mut TokenStream,
+    hooks: &mut TokenStream,
+) -> syn::Result<()> {
+    for attr in attrs {
+        if attr.path() != JSONFORMS {
+            continue;
+        }
+
+        if let syn::Meta::List(meta) = &attr.meta {
+            if meta.tokens.is_empty() {
+                continue;
+            }
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path == VALIDATE_WITH {
+                let lit = get_lit_str(&meta)?;
+                let path: syn::ExprPath = lit.parse()?;
+                hooks.extend(quote_spanned!(lit.span()=>
+                    if let Err(hook_errors) = #path(self) {
+                        for error in hook_errors {
+                            jsonforms_errors.push(JsonFormsError::new(
+                                format!("{}{}", jsonforms_pointer, error.pointer),
+                                error.message,
+                            ));
+                        }
+                    }
+                ));
+                return Ok(());
+            }
+            if meta.path != CONDITIONAL {
+                return attr::skip_meta(&meta);
+            }
+            let mut condition = None;
+            let mut branches = Vec::new();
+            meta.parse_nested_meta(|meta| {
+                if meta.path == IF {
+                    condition = Some(parse_condition(&meta, siblings)?);
+                } else if meta.path == THEN || meta.path == ELSE {
+                    let key = if meta.path == THEN { "then" } else { "else" };
+                    let lit = get_lit_str(&meta)?;
+                    let Ok(serde_json::Value::Object(schema)) = serde_json::from_str(&lit.value())
+                    else {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            format!("`{}` must be a json object", key),
+                        ));
+                    };
+                    let checks = branch_checks(key, &schema, &lit, siblings)?;
+                    branches.push((key, lit.value(), checks));
+                } else {
+                    return Err(meta.error("expected `if(..)`, `then` or `else`"));
+                }
+                Ok(())
+            })?;
+            let Some(condition) = condition else {
+                return Err(meta.error("conditional needs an `if(..)`"));
+            };
+            if branches.is_empty() {
+                return Err(meta.error("conditional needs a `then` or an `else`"));
+            }
+            let check = condition.to_check()?;
+            let mut block = format!(r#"{{"if":{}"#, condition.to_schema());
+            let mut then_check = TokenStream::new();
+            let mut else_check = TokenStream::new();
+            for (key, schema, branch_checks) in branches {
+                block += &format!(r#","{}":{}"#, key, schema);
+                if key == "then" {
+                    then_check = branch_checks;
+                } else {
+                    else_check = branch_checks;
+                }
+            }
+            block += "}";
+            all_of.push(block);
+            checks.extend(quote!(
+                if #check {
+                    #then_check
+                } else {
+                    #else_check
+                }
+            ));
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+/// server side checks of a `then` or `else` schema: the `required` fields and
+/// the checked keywords of its `properties`. Other keywords would only be
+/// enforced by the client
+fn branch_checks(
+    key: &str,
+    schema: &serde_json::Map<String, serde_json::Value>,
+    lit: &syn::LitStr,
+    siblings: &[Sibling],
+) -> syn::Result<TokenStream> {
+    let sibling = |name: &str| {
+        siblings
+            .iter()
+            .find(|sibling| sibling.name == name)
+            .ok_or_else(|| syn::Error::new(lit.span(), format!("unknown field `{}`", name)))
+    };
+    let mut checks = TokenStream::new();
+    for (keyword, value) in schema {
+        match (keyword.as_str(), value) {
+            ("required", serde_json::Value::Array(names)) => {
+                for name in names {
+                    let sibling = sibling(name.as_str().unwrap_or_default())?;
+                    if sibling.is_option {
+                        checks.extend(required_check(sibling, "is required"));
+                    }
+                }
+            }
+            ("properties", serde_json::Value::Object(properties)) => {
+                for (name, constraints) in properties {
+                    let sibling = sibling(name)?;
+                    let Some(constraints) = constraints.as_object() else {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            format!("the `{}` schema of `{}` must be a json object", key, name),
+                        ));
+                    };
+                    for (keyword, limit) in constraints {
+                        if !CHECKED_KEYWORDS.contains(&keyword.as_str()) || limit.get("$data").is_some() {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                format!("`{}` in `{}` can't be checked by the server", keyword, key),
+                            ));
+                        }
+                    }
+                    if let Some(pattern) = constraints.get("pattern").and_then(|p| p.as_str()) {
+                        check_pattern(pattern, lit.span())?;
+                    }
+                    if let Some(format) = constraints.get("format").and_then(|f| f.as_str()) {
+                        check_format(format, lit.span())?;
+                    }
+                    let ident = sibling.ident;
+                    let pointer = json_pointer_expr(&sibling.name);
+                    let constraints = serde_json::Value::Object(constraints.clone()).to_string();
+                    let check = quote!(
+                        jsonforms_check_value(#ident, #constraints, &#pointer, jsonforms_errors);
+                    );
+                    checks.extend(if sibling.is_option {
+                        quote!(
+                            if let Some(#ident) = #ident {
+                                #check
+                            }
+                        )
+                    } else {
+                        check
+                    });
+                }
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "`{}` in `{}` can't be checked by the server, expected `required` or `properties`",
+                        keyword, key
+                    ),
+                ))
+            }
+        }
+    }
+    Ok(checks)
+}
+
+/// error at `field` if it is `None`
+fn required_check(field: &Sibling, message: &str) -> TokenStream {
+    let ident = field.ident;
+    let pointer = json_pointer_expr(&field.name);
+    quote!(
+        if #ident.is_none() {
+            jsonforms_errors.push(JsonFormsError::new(#pointer, #message));
+        }
+    )
+}
+
+fn has_skip(attrs: &[syn::Attribute]) -> Result<bool, Vec<syn::Error>> {
+    let mut skip = false;
+    for attr in attrs {
+        if attr.path() != JSONFORMS {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path == SKIP {
+                skip = true;
+            } else {
+                attr::skip_meta(&meta)?;
+            }
+            Ok(())
+        })
+        .map_err(|err| vec![err])?;
     }
-    out += "}";
-    uiout += "]}";
-    out_tokens.extend(quote!( out_str += #out; ));
-    uiout_tokens.extend(quote!(uiout_str += #uiout; ));
-    Ok((out_tokens, uiout_tokens))
+    Ok(skip)
+}
+
+/// builds the `out_str += ..` statements, adjacent literals are merged
+pub(crate) struct OutTokens {
+    var: Ident,
+    lit: String,
+    tokens: TokenStream,
+}
+
+impl OutTokens {
+    pub(crate) fn new(var: &str) -> Self {
+        Self {
+            var: Ident::new(var, Span::call_site()),
+            lit: String::new(),
+            tokens: TokenStream::new(),
+        }
+    }
+
+    pub(crate) fn push_str(&mut self, s: &str) {
+        self.lit += s;
+    }
+
+    pub(crate) fn push_tokens(&mut self, tokens: TokenStream) {
+        self.flush();
+        self.tokens.extend(tokens);
+    }
+
+    fn flush(&mut self) {
+        if !self.lit.is_empty() {
+            let var = &self.var;
+            let lit = &self.lit;
+            self.tokens.extend(quote!( #var += #lit; ));
+            self.lit.clear();
+        }
+    }
+
+    pub(crate) fn into_tokens(mut self) -> TokenStream {
+        self.flush();
+        self.tokens
+    }
+}
+
+pub(crate) fn json_str(s: &str) -> String {
+    serde_json::Value::from(s).to_string()
+}
+
+/// `name` escaped as a json pointer reference token
+fn json_pointer_token(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+/// the field `name` in a uischema scope written inside a json string
+pub(crate) fn scope_token(name: &str) -> String {
+    let quoted = json_str(&json_pointer_token(name));
+    quoted[1..quoted.len() - 1].to_owned()
+}
+
+/// json pointer of the field `name`, below the runtime `jsonforms_pointer`
+fn json_pointer_expr(name: &str) -> TokenStream {
+    let token = format!("/{}", json_pointer_token(name));
+    quote!(format!("{}{}", jsonforms_pointer, #token))
 }
 
 #[derive(Default)]
-struct MyType<'a>(Option<&'static str>, Option<&'a syn::Type>, bool);
+struct MyType<'a> {
+    json_type: Option<&'static str>,
+    items: Option<Box<MyType<'a>>>,
+    ref_type: Option<&'a syn::Type>,
+    range: Option<IntRange>,
+    unique_items: bool,
+    is_option: bool,
+}
+
+/// primitive integer type, its MIN and MAX bound the schema
+struct IntRange {
+    prim: &'static str,
+    non_zero: bool,
+}
+
+const INT_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
 
-fn get_type(ty: &syn::Type) -> MyType {
+impl<'a> MyType<'a> {
+    fn json(json_type: &'static str) -> Self {
+        Self {
+            json_type: Some(json_type),
+            ..Self::default()
+        }
+    }
+
+    fn integer(prim: &str, non_zero: bool) -> Self {
+        Self {
+            range: INT_TYPES
+                .iter()
+                .find(|int| **int == prim)
+                .map(|prim| IntRange { prim, non_zero }),
+            ..Self::json("integer")
+        }
+    }
+
+    /// arrays and maps of `items`
+    fn collection(json_type: &'static str, items: MyType<'a>, unique_items: bool) -> Self {
+        Self {
+            items: Some(Box::new(items)),
+            unique_items,
+            ..Self::json(json_type)
+        }
+    }
+
+    /// the schema member holding `items`, maps hold it per property
+    fn item_key(&self) -> &'static str {
+        if self.json_type == Some("object") {
+            "additionalProperties"
+        } else {
+            "items"
+        }
+    }
+
+    /// a type the derive does not know, its schema comes from JsonFormsField
+    fn reference(ty: &'a syn::Type) -> Self {
+        Self {
+            ref_type: Some(ty),
+            ..Self::default()
+        }
+    }
+}
+
+fn get_type(ty: &syn::Type) -> MyType<'_> {
     match ty {
-        syn::Type::Path(ty) => {
-            if let Some(seg) = ty.path.segments.last() {
+        syn::Type::Path(type_path) => {
+            if let Some(seg) = type_path.path.segments.last() {
                 let last_name = seg.ident.to_string();
                 let arguments = &seg.arguments;
                 match last_name.as_str() {
-                    "String" => MyType(Some("string"), None, false),
-                    "i32" | "u32" | "u64" | "i64" => MyType(Some("integer"), None, false),
-                    "f32" | "f64" => MyType(Some("number"), None, false),
-                    "bool" => MyType(Some("boolean"), None, false),
-                    "Vec" => {
+                    "String" => MyType::json("string"),
+                    int if INT_TYPES.contains(&int) => MyType::integer(int, false),
+                    // NonZeroU32 and NonZero<u32>
+                    "NonZero" => match arguments {
+                        syn::PathArguments::AngleBracketed(arguments) => {
+                            match arguments.args.first() {
+                                Some(syn::GenericArgument::Type(syn::Type::Path(arg))) => {
+                                    match arg.path.get_ident().map(|ident| ident.to_string()) {
+                                        Some(int) if INT_TYPES.contains(&int.as_str()) => {
+                                            MyType::integer(&int, true)
+                                        }
+                                        _ => MyType::reference(ty),
+                                    }
+                                }
+                                _ => MyType::reference(ty),
+                            }
+                        }
+                        _ => MyType::reference(ty),
+                    },
+                    non_zero
+                        if non_zero.starts_with("NonZero")
+                            && INT_TYPES.contains(&non_zero[7..].to_lowercase().as_str()) =>
+                    {
+                        MyType::integer(&non_zero[7..].to_lowercase(), true)
+                    }
+                    "f32" | "f64" => MyType::json("number"),
+                    "bool" => MyType::json("boolean"),
+                    "Vec" | "VecDeque" | "HashSet" | "BTreeSet" => {
                         match arguments {
-                            syn::PathArguments::None => MyType::default(),
+                            syn::PathArguments::None => MyType::reference(ty),
                             syn::PathArguments::AngleBracketed(arguments) => {
-                                let mut ret: MyType = MyType::default();
+                                let mut ret = MyType::reference(ty);
                                 for arg in &arguments.args {
                                     if let syn::GenericArgument::Type(arg) = arg {
-                                        ret = MyType(Some("array"), Some(arg), false);
+                                        ret = MyType::collection(
+                                            "array",
+                                            get_type(arg),
+                                            last_name.ends_with("Set"),
+                                        );
                                         break;
                                     }
                                 }
@@ -260,19 +2165,32 @@ fn get_type(ty: &syn::Type) -> MyType {
                                     // self.visit_type(argument);
                                 }
                                 // self.visit_return_type(&arguments.output);
-                                MyType::default()
+                                MyType::reference(ty)
                             }
                         }
                     }
-                    "Option" => {
+                    // keys are strings in json, values follow the value type
+                    "HashMap" | "BTreeMap" => match arguments {
+                        syn::PathArguments::AngleBracketed(arguments) => {
+                            match arguments.args.iter().nth(1) {
+                                Some(syn::GenericArgument::Type(arg)) => {
+                                    MyType::collection("object", get_type(arg), false)
+                                }
+                                _ => MyType::reference(ty),
+                            }
+                        }
+                        _ => MyType::reference(ty),
+                    },
+                    // a Box has the schema of its value, recursive types need it
+                    "Option" | "Box" => {
                         match arguments {
-                            syn::PathArguments::None => MyType::default(),
+                            syn::PathArguments::None => MyType::reference(ty),
                             syn::PathArguments::AngleBracketed(arguments) => {
-                                let mut ret: MyType = MyType::default();
+                                let mut ret = MyType::reference(ty);
                                 for arg in &arguments.args {
                                     if let syn::GenericArgument::Type(arg) = arg {
                                         ret = get_type(arg);
-                                        ret.2 = true;
+                                        ret.is_option |= last_name == "Option";
                                         break;
                                     }
                                 }
@@ -283,33 +2201,140 @@ fn get_type(ty: &syn::Type) -> MyType {
                                     // self.visit_type(argument);
                                 }
                                 // self.visit_return_type(&arguments.output);
-                                MyType::default()
+                                MyType::reference(ty)
                             }
                         }
                     }
-                    _ => MyType::default(),
+                    // any other type must implement JsonFormsField, e.g. another
+                    // struct or enum deriving JsonForms or a newtype
+                    _ => MyType::reference(ty),
                 }
             } else {
-                MyType::default()
+                MyType::reference(ty)
             }
         }
-        _ => MyType::default(),
+        _ => MyType::reference(ty),
     }
 }
 
-fn get_lit_str(meta: &ParseNestedMeta) -> syn::Result<Option<syn::LitStr>> {
+pub(crate) fn get_value_expr(meta: &ParseNestedMeta) -> syn::Result<syn::Expr> {
     let expr: syn::Expr = meta.value()?.parse()?;
-    let mut value = &expr;
+    let mut value = expr;
     while let syn::Expr::Group(e) = value {
-        value = &e.expr;
+        value = *e.expr;
     }
-    if let syn::Expr::Lit(syn::ExprLit {
-        lit: syn::Lit::Str(lit),
-        ..
-    }) = value
-    {
-        Ok(Some(lit.clone()))
-    } else {
-        Ok(None)
+    Ok(value)
+}
+
+/// `key = "str"`, returned as a json string
+pub(crate) fn get_json_str(meta: &ParseNestedMeta) -> syn::Result<String> {
+    Ok(json_str(&get_lit_str(meta)?.value()))
+}
+
+/// `key = 10`
+fn get_json_uint(meta: &ParseNestedMeta) -> syn::Result<String> {
+    match get_value_expr(meta)? {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => Ok(lit.base10_parse::<u64>()?.to_string()),
+        expr => Err(syn::Error::new(
+            expr.span(),
+            format!(
+                "expected unsigned integer literal for `{}`",
+                path_str(&meta.path)
+            ),
+        )),
+    }
+}
+
+/// `key = 10`, `key = -1.5`
+fn get_json_number(meta: &ParseNestedMeta) -> syn::Result<String> {
+    let expr = get_value_expr(meta)?;
+    json_number(&expr)?.ok_or_else(|| {
+        syn::Error::new(
+            expr.span(),
+            format!("expected number literal for `{}`", path_str(&meta.path)),
+        )
+    })
+}
+
+/// `key = "str"`, `key = true` or `key = -1.5`, returned as json
+pub(crate) fn get_json_literal(meta: &ParseNestedMeta) -> syn::Result<String> {
+    let expr = get_value_expr(meta)?;
+    match &expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => return Ok(json_str(&lit.value())),
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Bool(lit),
+            ..
+        }) => return Ok(lit.value.to_string()),
+        _ => (),
+    }
+    json_number(&expr)?.ok_or_else(|| {
+        syn::Error::new(
+            expr.span(),
+            format!("expected literal for `{}`", path_str(&meta.path)),
+        )
+    })
+}
+
+/// json of a number literal, possibly negated
+pub(crate) fn json_number(expr: &syn::Expr) -> syn::Result<Option<String>> {
+    let (neg, lit) = match expr {
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => (true, expr.as_ref()),
+        _ => (false, expr),
+    };
+    let number = match lit {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit.base10_parse::<i128>()?.to_string(),
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Float(lit),
+            ..
+        }) => lit.base10_parse::<f64>()?.to_string(),
+        _ => return Ok(None),
+    };
+    Ok(Some(if neg { format!("-{}", number) } else { number }))
+}
+
+/// `key` or `key = true`
+fn get_bool(meta: &ParseNestedMeta) -> syn::Result<bool> {
+    if !meta.input.peek(Token![=]) {
+        return Ok(true);
+    }
+    match get_value_expr(meta)? {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Bool(lit),
+            ..
+        }) => Ok(lit.value),
+        expr => Err(syn::Error::new(
+            expr.span(),
+            format!("expected boolean literal for `{}`", path_str(&meta.path)),
+        )),
+    }
+}
+
+fn path_str(path: &syn::Path) -> String {
+    quote!(#path).to_string()
+}
+
+pub(crate) fn get_lit_str(meta: &ParseNestedMeta) -> syn::Result<syn::LitStr> {
+    match get_value_expr(meta)? {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => Ok(lit),
+        expr => Err(syn::Error::new(
+            expr.span(),
+            format!("expected string literal for `{}`", path_str(&meta.path)),
+        )),
     }
 }
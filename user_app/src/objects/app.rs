@@ -7,6 +7,7 @@ use user_app_common::objects::app::MainResponse;
 use super::{ApiResponse, InfoResponse};
 
 #[derive(Deserialize, JsonForms)]
+#[jsonforms(title = "Main User Form")]
 pub struct MainRequest {
     pub app_str: String,
 }
@@ -39,6 +40,7 @@ pub fn handle_main_request(
 }
 
 #[derive(Deserialize, JsonForms)]
+#[jsonforms(title = "Dummy User Form")]
 pub struct DummyRequest {
     pub dummy_str: String,
 }
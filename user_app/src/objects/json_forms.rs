@@ -4,8 +4,8 @@ use jsonforms::json_forms::{JsonFormsSerializable};
 
 
 pub(crate) fn get(r: JsonFormsRequest) -> Result<InfoResponse, AppError> {
-    JsonFormGUI!(r.name.as_str(), app::MainRequest, "Main User Form");
-    JsonFormGUI!(r.name.as_str(), app::DummyRequest, "Dummy User Form");
+    JsonFormGUI!(r.name.as_str(), app::MainRequest);
+    JsonFormGUI!(r.name.as_str(), app::DummyRequest);
 
     let str_err = format!("user_app unknown requested json schema {}", r.name);
     log::error!("{str_err}");